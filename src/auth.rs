@@ -3,8 +3,15 @@
 use anyhow::{Context, Result};
 use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
 use serde::Serialize;
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Apple rejects tokens that live longer than 20 minutes.
+pub const MAX_TOKEN_TTL_SECS: u64 = 20 * 60;
+/// Mint a new token this long before the cached one expires, so a request
+/// started just before expiry doesn't arrive with a stale token.
+pub const REFRESH_MARGIN_SECS: u64 = 60;
+
 #[derive(Serialize)]
 struct Claims {
     iss: String,
@@ -13,26 +20,74 @@ struct Claims {
     aud: String,
 }
 
-/// Generate a short-lived ES256 JWT for the App Store Connect API.
-pub fn generate_token(issuer_id: &str, key_id: &str, private_key: &str) -> Result<String> {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .context("clock error")?
-        .as_secs();
+/// Signs App Store Connect JWTs with a key parsed once up front, and hands
+/// out the same token until it is close to expiring. Safe to share across
+/// concurrent requests.
+pub struct TokenSource {
+    issuer_id: String,
+    header: Header,
+    key: EncodingKey,
+    ttl_secs: u64,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    token: String,
+    expires_at: u64,
+}
+
+impl TokenSource {
+    /// `ttl_secs` is each token's lifetime; more than `REFRESH_MARGIN_SECS`
+    /// and at most `MAX_TOKEN_TTL_SECS`.
+    pub fn new(issuer_id: &str, key_id: &str, private_key: &str, ttl_secs: u64) -> Result<Self> {
+        let key = EncodingKey::from_ec_pem(private_key.as_bytes())
+            .context("failed to parse .p8 private key")?;
+
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(key_id.to_string());
+        header.typ = Some("JWT".to_string());
+
+        Ok(Self {
+            issuer_id: issuer_id.to_string(),
+            header,
+            key,
+            ttl_secs,
+            cached: Mutex::new(None),
+        })
+    }
 
-    let claims = Claims {
-        iss: issuer_id.to_string(),
-        iat: now,
-        exp: now + 20 * 60,
-        aud: "appstoreconnect-v1".to_string(),
-    };
+    /// A valid token, reusing the cached one while it has more than
+    /// `REFRESH_MARGIN_SECS` left.
+    pub fn token(&self) -> Result<String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("clock error")?
+            .as_secs();
 
-    let mut header = Header::new(Algorithm::ES256);
-    header.kid = Some(key_id.to_string());
-    header.typ = Some("JWT".to_string());
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ref c) = *cached {
+            if now + REFRESH_MARGIN_SECS < c.expires_at {
+                return Ok(c.token.clone());
+            }
+        }
 
-    let key = EncodingKey::from_ec_pem(private_key.as_bytes())
-        .context("failed to parse .p8 private key")?;
+        let claims = Claims {
+            iss: self.issuer_id.clone(),
+            iat: now,
+            exp: now + self.ttl_secs,
+            aud: "appstoreconnect-v1".to_string(),
+        };
+        let token = encode(&self.header, &claims, &self.key).context("failed to encode JWT")?;
+        *cached = Some(CachedToken {
+            token: token.clone(),
+            expires_at: claims.exp,
+        });
+        Ok(token)
+    }
 
-    encode(&header, &claims, &key).context("failed to encode JWT")
+    /// Drop the cached token, e.g. after Apple rejected it, so the next
+    /// request signs a fresh one.
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
 }
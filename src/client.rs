@@ -1,96 +1,166 @@
 //! HTTP client for the App Store Connect API v4.2.
 
-use anyhow::{bail, Context, Result};
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
-use tracing::debug;
+use anyhow::Result;
+use reqwest::header::{HeaderMap, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::time::Duration;
+use tracing::{debug, warn};
 
 use crate::auth;
+use crate::config::RetryConfig;
+use crate::error::AscError;
 use crate::types::*;
 
-const BASE: &str = "https://api.appstoreconnect.apple.com";
+/// Production API host; overridable via `[api] base_url` or `ASC_BASE_URL`.
+pub const DEFAULT_BASE_URL: &str = "https://api.appstoreconnect.apple.com";
 
 pub struct AscClient {
     http: reqwest::Client,
-    issuer_id: String,
-    key_id: String,
-    private_key: String,
+    base: String,
+    tokens: auth::TokenSource,
+    retry: RetryConfig,
 }
 
 impl AscClient {
-    pub fn new(issuer_id: String, key_id: String, private_key: String) -> Result<Self> {
+    pub fn new(
+        base_url: &str,
+        issuer_id: String,
+        key_id: String,
+        private_key: String,
+        token_ttl_secs: u64,
+        retry: RetryConfig,
+    ) -> Result<Self> {
         let http = reqwest::Client::builder()
             .user_agent("asc-crash-fetcher/0.2.0")
             .build()?;
         Ok(Self {
             http,
-            issuer_id,
-            key_id,
-            private_key,
+            base: base_url.trim_end_matches('/').to_string(),
+            tokens: auth::TokenSource::new(&issuer_id, &key_id, &private_key, token_ttl_secs)?,
+            retry,
         })
     }
 
-    fn token(&self) -> Result<String> {
-        auth::generate_token(&self.issuer_id, &self.key_id, &self.private_key)
+    /// Authenticated GET, retrying rate limits (429), server errors (5xx) and
+    /// connection failures with exponential backoff. Returns the final
+    /// response whatever its status; callers decide what counts as success.
+    async fn send(&self, url: &str, json: bool) -> Result<reqwest::Response> {
+        let mut attempt = 1;
+        loop {
+            let token = self.tokens.token()?;
+            debug!(url, attempt, "GET");
+            let mut req = self
+                .http
+                .get(url)
+                .header(AUTHORIZATION, format!("Bearer {token}"));
+            if json {
+                req = req.header(CONTENT_TYPE, "application/json");
+            }
+
+            let (delay, reason) = match req.send().await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if status == StatusCode::UNAUTHORIZED {
+                        self.tokens.invalidate();
+                    }
+                    let limit = RateLimit::parse(resp.headers());
+                    if let Some(ref l) = limit {
+                        debug!(remaining = l.remaining, limit = l.limit, "rate limit");
+                        if l.remaining * 20 < l.limit {
+                            warn!(
+                                remaining = l.remaining,
+                                limit = l.limit,
+                                "approaching App Store Connect hourly rate limit"
+                            );
+                        }
+                    }
+                    if !is_retryable(status) || attempt >= self.retry.max_attempts {
+                        return Ok(resp);
+                    }
+                    let delay = match retry_after(resp.headers()) {
+                        Some(d) => d.min(self.retry.max_delay()),
+                        None => self.backoff(attempt),
+                    };
+                    // With the hourly quota spent and no hint from Apple,
+                    // retrying within our backoff window can't succeed.
+                    if status == StatusCode::TOO_MANY_REQUESTS
+                        && limit.is_some_and(|l| l.remaining == 0)
+                        && resp.headers().get(RETRY_AFTER).is_none()
+                    {
+                        return Ok(resp);
+                    }
+                    (delay, status.to_string())
+                }
+                Err(e)
+                    if (e.is_timeout() || e.is_connect()) && attempt < self.retry.max_attempts =>
+                {
+                    (self.backoff(attempt), e.to_string())
+                }
+                Err(e) => return Err(AscError::Network(e).into()),
+            };
+
+            warn!(
+                url,
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                reason,
+                "retrying request"
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Exponential backoff with jitter: a random delay between half and all
+    /// of `base * 2^(attempt-1)`, capped at the configured maximum.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .retry
+            .base_delay()
+            .saturating_mul(1 << (attempt - 1).min(16))
+            .min(self.retry.max_delay());
+        let half = exp / 2;
+        half + half.mul_f64(jitter())
     }
 
     async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
-        let token = self.token()?;
-        debug!(url, "GET");
-        let resp = self
-            .http
-            .get(url)
-            .header(AUTHORIZATION, format!("Bearer {token}"))
-            .header(CONTENT_TYPE, "application/json")
-            .send()
-            .await
-            .context("request failed")?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            bail!("API {status}: {body}");
+        let resp = self.send(url, true).await?;
+
+        if !resp.status().is_success() {
+            return Err(error_from(resp).await.into());
         }
-        resp.json().await.context("parse response")
+        Ok(resp.json().await.map_err(AscError::Decode)?)
     }
 
     /// GET that returns None on 404 (for optional endpoints like crash logs).
     async fn get_optional<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<Option<T>> {
-        let token = self.token()?;
-        debug!(url, "GET (optional)");
-        let resp = self
-            .http
-            .get(url)
-            .header(AUTHORIZATION, format!("Bearer {token}"))
-            .header(CONTENT_TYPE, "application/json")
-            .send()
-            .await
-            .context("request failed")?;
+        let resp = self.send(url, true).await?;
 
         if resp.status().as_u16() == 404 {
             return Ok(None);
         }
-        let status = resp.status();
-        if !status.is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            bail!("API {status}: {body}");
+        if !resp.status().is_success() {
+            return Err(error_from(resp).await.into());
         }
-        let val: T = resp.json().await?;
+        let val: T = resp.json().await.map_err(AscError::Decode)?;
         Ok(Some(val))
     }
 
     // ─── Apps ────────────────────────────────────────────────────────────
 
     pub async fn list_apps(&self) -> Result<Vec<App>> {
+        let base = &self.base;
         let r: AppsResponse = self
-            .get_json(&format!("{BASE}/v1/apps?fields[apps]=name,bundleId"))
+            .get_json(&format!("{base}/v1/apps?fields[apps]=name,bundleId"))
             .await?;
         Ok(r.data)
     }
 
     pub async fn find_app(&self, bundle_id: &str) -> Result<Option<App>> {
+        let base = &self.base;
         let r: AppsResponse = self
             .get_json(&format!(
-                "{BASE}/v1/apps?filter[bundleId]={bundle_id}&fields[apps]=name,bundleId"
+                "{base}/v1/apps?filter[bundleId]={bundle_id}&fields[apps]=name,bundleId"
             ))
             .await?;
         Ok(r.data.into_iter().next())
@@ -103,9 +173,10 @@ impl AscClient {
     }
 
     /// Build the initial URL for listing crash submissions.
-    pub fn crash_list_url(app_asc_id: &str) -> String {
+    pub fn crash_list_url(&self, app_asc_id: &str) -> String {
+        let base = &self.base;
         format!(
-            "{BASE}/v1/apps/{app_asc_id}/betaFeedbackCrashSubmissions\
+            "{base}/v1/apps/{app_asc_id}/betaFeedbackCrashSubmissions\
              ?fields[betaFeedbackCrashSubmissions]=\
              createdDate,comment,email,deviceModel,osVersion,locale,\
              timeZone,architecture,connectionType,appUptimeInMilliseconds,\
@@ -119,8 +190,9 @@ impl AscClient {
 
     /// Download the crash log text for a submission. Returns None if not yet available.
     pub async fn get_crash_log(&self, submission_id: &str) -> Result<Option<String>> {
+        let base = &self.base;
         let url = format!(
-            "{BASE}/v1/betaFeedbackCrashSubmissions/{submission_id}/crashLog\
+            "{base}/v1/betaFeedbackCrashSubmissions/{submission_id}/crashLog\
              ?fields[betaCrashLogs]=logText"
         );
         let resp: Option<CrashLogResponse> = self.get_optional(&url).await?;
@@ -129,6 +201,22 @@ impl AscClient {
             .and_then(|a| a.log_text))
     }
 
+    // ─── Builds ──────────────────────────────────────────────────────────
+
+    /// Fetch a build with its pre-release (marketing) version. Returns None
+    /// if the build no longer exists.
+    pub async fn get_build(&self, build_id: &str) -> Result<Option<BuildResponse>> {
+        let base = &self.base;
+        let url = format!(
+            "{base}/v1/builds/{build_id}\
+             ?include=preReleaseVersion\
+             &fields[builds]=version,uploadedDate,expirationDate,expired,\
+             minOsVersion,preReleaseVersion\
+             &fields[preReleaseVersions]=version,platform"
+        );
+        self.get_optional(&url).await
+    }
+
     // ─── Screenshot submissions ───────────────────────────────────────────
 
     pub async fn get_screenshot_page(&self, url: &str) -> Result<ScreenshotSubmissionsResponse> {
@@ -136,9 +224,10 @@ impl AscClient {
     }
 
     /// Build the initial URL for listing screenshot submissions.
-    pub fn screenshot_list_url(app_asc_id: &str) -> String {
+    pub fn screenshot_list_url(&self, app_asc_id: &str) -> String {
+        let base = &self.base;
         format!(
-            "{BASE}/v1/apps/{app_asc_id}/betaFeedbackScreenshotSubmissions\
+            "{base}/v1/apps/{app_asc_id}/betaFeedbackScreenshotSubmissions\
              ?fields[betaFeedbackScreenshotSubmissions]=\
              createdDate,comment,email,deviceModel,osVersion,locale,\
              timeZone,connectionType,batteryPercentage,\
@@ -150,25 +239,15 @@ impl AscClient {
 
     /// Download screenshot. Returns Option<(bytes, mime_type)>.
     pub async fn get_screenshot(&self, submission_id: &str) -> Result<Option<(Vec<u8>, String)>> {
-        let url = format!("{BASE}/v1/betaFeedbackScreenshotSubmissions/{submission_id}/screenshot");
-        let token = self.token()?;
-        debug!(url = %url, "GET screenshot");
-
-        let resp = self
-            .http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {token}"))
-            .send()
-            .await
-            .context("screenshot request failed")?;
+        let base = &self.base;
+        let url = format!("{base}/v1/betaFeedbackScreenshotSubmissions/{submission_id}/screenshot");
+        let resp = self.send(&url, false).await?;
 
         if resp.status().as_u16() == 404 {
             return Ok(None);
         }
-        let status = resp.status();
-        if !status.is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            bail!("API {status}: {body}");
+        if !resp.status().is_success() {
+            return Err(error_from(resp).await.into());
         }
 
         let content_type = resp
@@ -178,7 +257,65 @@ impl AscClient {
             .unwrap_or("image/png")
             .to_string();
 
-        let bytes = resp.bytes().await.context("read screenshot bytes")?;
+        let bytes = resp.bytes().await.map_err(AscError::Network)?;
         Ok(Some((bytes.to_vec(), content_type)))
     }
 }
+
+/// Turn a failed response into a typed error.
+async fn error_from(resp: reqwest::Response) -> AscError {
+    let status = resp.status();
+    let retry_after = retry_after(resp.headers());
+    let body = resp.text().await.unwrap_or_default();
+    AscError::from_response(status, retry_after, &body)
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// `Retry-After`, in either delta-seconds or HTTP-date form.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let secs = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+    Some(Duration::from_secs(secs.max(0) as u64))
+}
+
+/// Apple's `X-Rate-Limit: user-hour-lim:3600;user-hour-rem:3542;` header.
+#[derive(Debug, Clone, Copy)]
+struct RateLimit {
+    limit: u64,
+    remaining: u64,
+}
+
+impl RateLimit {
+    fn parse(headers: &HeaderMap) -> Option<Self> {
+        let value = headers.get("x-rate-limit")?.to_str().ok()?;
+        let mut limit = None;
+        let mut remaining = None;
+        for part in value.split(';') {
+            match part.trim().split_once(':') {
+                Some(("user-hour-lim", v)) => limit = v.trim().parse().ok(),
+                Some(("user-hour-rem", v)) => remaining = v.trim().parse().ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            limit: limit?,
+            remaining: remaining?,
+        })
+    }
+}
+
+/// A value in [0, 1) for backoff jitter. `RandomState` is randomly seeded
+/// per instance, which is plenty for spreading out retries.
+fn jitter() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let mut h = std::collections::hash_map::RandomState::new().build_hasher();
+    h.write_u64(0);
+    (h.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -6,8 +6,12 @@
 //!   3. `~/.asc-crashes/`  (global default)
 
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::auth;
 
 /// Top-level config from `config.toml`.
 #[derive(Debug, Deserialize, Clone)]
@@ -15,6 +19,18 @@ pub struct Config {
     pub api: ApiConfig,
     #[serde(default)]
     pub apps: Vec<AppEntry>,
+    #[serde(default)]
+    pub symbolication: SymbolicationConfig,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    #[serde(default)]
+    pub sync: SyncConfig,
+    #[serde(default)]
+    pub user: UserConfig,
+    #[serde(default)]
+    pub workflow: WorkflowConfig,
+    #[serde(default)]
+    pub views: Vec<ViewConfig>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -22,6 +38,212 @@ pub struct ApiConfig {
     pub issuer_id: String,
     pub key_id: String,
     pub private_key: String,
+    /// API host, for proxies and test servers. `ASC_BASE_URL` overrides it.
+    pub base_url: Option<String>,
+    /// Lifetime of each signed JWT. Default and maximum: 1200 (20 minutes).
+    pub token_ttl_secs: Option<u64>,
+}
+
+/// `[retry]` — how hard to retry rate-limited (429) and failed (5xx,
+/// connection error) API requests.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Total tries per request, including the first. 1 disables retries.
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubles on each attempt.
+    pub base_delay_ms: u64,
+    /// Upper bound for any single wait, including `Retry-After`.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay_ms: 1_000,
+            max_delay_ms: 60_000,
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn base_delay(&self) -> Duration {
+        Duration::from_millis(self.base_delay_ms)
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        Duration::from_millis(self.max_delay_ms)
+    }
+}
+
+/// `[sync]` — how `sync` talks to the API.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Crash logs/screenshots downloaded at once.
+    pub concurrency: usize,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self { concurrency: 4 }
+    }
+}
+
+/// `[user]` — who is making status changes, for the history log.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct UserConfig {
+    pub name: Option<String>,
+}
+
+/// Statuses the built-in commands and `sync` move items to.
+const REQUIRED_STATUSES: [&str; 3] = ["new", "fixed", "duplicate"];
+
+/// `[workflow]` — the statuses crashes and feedback move through.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WorkflowConfig {
+    /// Every allowed status. Must include `new`, `fixed` and `duplicate`.
+    pub statuses: Vec<String>,
+    /// Statuses that count as unresolved: "unfixed" counts, assignee queues,
+    /// and the crashes `groups fix` closes.
+    pub open: Vec<String>,
+    /// Allowed next statuses, keyed by current status. A status without an
+    /// entry can move to any other.
+    pub transitions: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        Self {
+            statuses: ["new", "investigating", "fixed", "wontfix", "duplicate"]
+                .map(String::from)
+                .to_vec(),
+            open: vec!["new".into(), "investigating".into()],
+            transitions: BTreeMap::new(),
+        }
+    }
+}
+
+impl WorkflowConfig {
+    fn validate(&self) -> Result<()> {
+        for s in &self.statuses {
+            if s.is_empty()
+                || !s
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "[workflow] invalid status '{s}': use lowercase letters, digits, '-' and '_'"
+                );
+            }
+        }
+        for s in REQUIRED_STATUSES {
+            if !self.is_status(s) {
+                anyhow::bail!("[workflow] statuses must include '{s}'");
+            }
+        }
+        let listed = self
+            .open
+            .iter()
+            .chain(self.transitions.keys())
+            .chain(self.transitions.values().flatten());
+        for s in listed {
+            if !self.is_status(s) {
+                anyhow::bail!("[workflow] '{s}' is not in statuses");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_status(&self, status: &str) -> bool {
+        self.statuses.iter().any(|s| s == status)
+    }
+
+    pub fn is_open(&self, status: &str) -> bool {
+        self.open.iter().any(|s| s == status)
+    }
+
+    /// Fail unless `status` is defined.
+    pub fn check_status(&self, status: &str) -> Result<()> {
+        if !self.is_status(status) {
+            anyhow::bail!(
+                "unknown status '{status}'; expected one of: {}",
+                self.statuses.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Fail unless an item in `from` may move to `to`.
+    pub fn check_transition(&self, from: &str, to: &str) -> Result<()> {
+        self.check_status(to)?;
+        match self.transitions.get(from) {
+            Some(allowed) if from != to && !allowed.iter().any(|s| s == to) => {
+                anyhow::bail!(
+                    "can't move from '{from}' to '{to}'; allowed: {}",
+                    if allowed.is_empty() {
+                        "none".to_string()
+                    } else {
+                        allowed.join(", ")
+                    }
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `[[views]]` — a saved `list` / `feedback list` query, run with `view <name>`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ViewConfig {
+    pub name: String,
+    #[serde(default)]
+    pub entity: ViewEntity,
+    /// A `--where` expression.
+    pub filter: Option<String>,
+    /// A `--sort` list, e.g. "battery_pct,created_at:desc".
+    pub sort: Option<String>,
+    /// Fields to show, by JSON name. Empty shows the usual list output.
+    #[serde(default)]
+    pub columns: Vec<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewEntity {
+    #[default]
+    Crashes,
+    Feedback,
+}
+
+impl ViewConfig {
+    /// Check the filter and sort parse, so a typo fails at load rather than
+    /// when someone runs the view. Field names are checked when it runs.
+    fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            anyhow::bail!("[[views]] entries need a name");
+        }
+        if let Some(ref filter) = self.filter {
+            crate::query::parse(filter)
+                .with_context(|| format!("[[views]] '{}': invalid filter", self.name))?;
+        }
+        if let Some(ref sort) = self.sort {
+            crate::query::parse_sort(sort)
+                .with_context(|| format!("[[views]] '{}': invalid sort", self.name))?;
+        }
+        Ok(())
+    }
+}
+
+/// `[symbolication]` — where to look for dSYM bundles.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SymbolicationConfig {
+    /// Directory searched recursively for `*.dSYM` bundles. Relative paths
+    /// are resolved against the data directory.
+    pub dsym_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -41,6 +263,50 @@ impl Config {
             .with_context(|| format!("invalid TOML in {}", path.display()))?;
 
         cfg.api.private_key = resolve_key(&cfg.api.private_key, data_dir)?;
+        if let Ok(url) = std::env::var("ASC_BASE_URL") {
+            if !url.is_empty() {
+                cfg.api.base_url = Some(url);
+            }
+        }
+        if let Some(ttl) = cfg.api.token_ttl_secs {
+            if ttl <= auth::REFRESH_MARGIN_SECS || ttl > auth::MAX_TOKEN_TTL_SECS {
+                anyhow::bail!(
+                    "[api] token_ttl_secs must be between {} and {} in {}",
+                    auth::REFRESH_MARGIN_SECS + 1,
+                    auth::MAX_TOKEN_TTL_SECS,
+                    path.display()
+                );
+            }
+        }
+        if cfg.retry.max_attempts == 0 {
+            anyhow::bail!(
+                "[retry] max_attempts must be at least 1 in {}",
+                path.display()
+            );
+        }
+        if cfg.sync.concurrency == 0 {
+            anyhow::bail!(
+                "[sync] concurrency must be at least 1 in {}",
+                path.display()
+            );
+        }
+        cfg.workflow
+            .validate()
+            .with_context(|| format!("invalid config in {}", path.display()))?;
+        for (i, view) in cfg.views.iter().enumerate() {
+            view.validate()
+                .with_context(|| format!("invalid config in {}", path.display()))?;
+            if cfg.views[..i].iter().any(|v| v.name == view.name) {
+                anyhow::bail!(
+                    "[[views]] '{}' is defined twice in {}",
+                    view.name,
+                    path.display()
+                );
+            }
+        }
+        if let Some(ref dir) = cfg.symbolication.dsym_dir {
+            cfg.symbolication.dsym_dir = Some(resolve_path(&dir.to_string_lossy(), data_dir));
+        }
 
         if cfg.apps.is_empty() {
             anyhow::bail!(
@@ -53,6 +319,16 @@ impl Config {
 
         Ok(cfg)
     }
+
+    /// Name recorded against status changes: `ASC_ACTOR`, then `[user] name`,
+    /// then `$USER`.
+    pub fn actor(&self) -> String {
+        let env = |key| std::env::var(key).ok().filter(|v: &String| !v.is_empty());
+        env("ASC_ACTOR")
+            .or_else(|| self.user.name.clone())
+            .or_else(|| env("USER"))
+            .unwrap_or_else(|| "unknown".to_string())
+    }
 }
 
 /// Resolve the data directory.
@@ -88,15 +364,7 @@ fn resolve_key(value: &str, relative_to: &Path) -> Result<String> {
         return Ok(value.to_string());
     }
 
-    // Expand ~ and resolve relative paths
-    let expanded = shellexpand::tilde(value);
-    let path = Path::new(expanded.as_ref());
-    let path = if path.is_relative() {
-        relative_to.join(path)
-    } else {
-        path.to_path_buf()
-    };
-
+    let path = resolve_path(value, relative_to);
     if path.exists() {
         std::fs::read_to_string(&path)
             .with_context(|| format!("could not read key file: {}", path.display()))
@@ -109,6 +377,17 @@ fn resolve_key(value: &str, relative_to: &Path) -> Result<String> {
     }
 }
 
+/// Expand `~` and resolve relative paths against `relative_to`.
+fn resolve_path(value: &str, relative_to: &Path) -> PathBuf {
+    let expanded = shellexpand::tilde(value);
+    let path = Path::new(expanded.as_ref());
+    if path.is_relative() {
+        relative_to.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
 /// Template config for `init`.
 pub const CONFIG_TEMPLATE: &str = r#"# asc-crash-fetcher configuration
 #
@@ -119,6 +398,8 @@ pub const CONFIG_TEMPLATE: &str = r#"# asc-crash-fetcher configuration
 issuer_id = "YOUR_ISSUER_ID"
 key_id    = "YOUR_KEY_ID"
 private_key = "path/to/AuthKey_XXXXXXXX.p8"
+# base_url = "https://api.appstoreconnect.apple.com"  # or set ASC_BASE_URL
+# token_ttl_secs = 1200  # JWT lifetime, 61-1200; renewed a minute before expiry
 
 # Add one or more apps to monitor for TestFlight crashes.
 # Use `asc-crash-fetcher apps` to verify your key works.
@@ -126,4 +407,46 @@ private_key = "path/to/AuthKey_XXXXXXXX.p8"
 [[apps]]
 bundle_id = "com.example.myapp"
 # name = "My App"  # optional friendly label
+
+# Retries for rate-limited (429) and failed (5xx) API requests.
+# [retry]
+# max_attempts = 5       # total tries per request; 1 disables retries
+# base_delay_ms = 1000   # first backoff, doubled each retry (with jitter)
+# max_delay_ms = 60000   # cap on any single wait, including Retry-After
+
+# Parallel crash log and screenshot downloads during `sync` (or `sync --jobs N`).
+# [sync]
+# concurrency = 4
+
+# Name recorded in status history (`fix`, `reopen`, …). Defaults to $USER;
+# ASC_ACTOR overrides it.
+# [user]
+# name = "alice"
+
+# Statuses crashes and feedback move through, for `status <id> <name>`.
+# `new`, `fixed` and `duplicate` are required; `open` ones count as unfixed.
+# A status listed under transitions may only move to the statuses given.
+# [workflow]
+# statuses = ["new", "investigating", "needs-info", "fixed",
+#             "fixed-pending-release", "wontfix", "cannot-reproduce", "duplicate"]
+# open = ["new", "investigating", "needs-info"]
+# [workflow.transitions]
+# fixed-pending-release = ["fixed", "new"]
+
+# Saved queries, run with `view <name>` (`view` lists them). `filter` and
+# `sort` take the same syntax as `list --where` / `--sort`; `columns` picks the
+# fields shown. `entity = "feedback"` queries feedback instead of crashes.
+# [[views]]
+# name = "my-queue"
+# filter = 'assignee = "alice" and status in ("new", "investigating")'
+# sort = "created_at:desc"
+# columns = ["id", "status", "device_model", "os_version", "signature"]
+#
+# [[views]]
+# name = "unsymbolicated"
+# filter = "has_log = true and symbolicated_log_path is null"
+
+# Local dSYM bundles for offline symbolication (`symbolicate`, `sync --symbolicate`).
+# [symbolication]
+# dsym_dir = "dsyms"
 "#;
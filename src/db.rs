@@ -6,13 +6,150 @@
 use anyhow::{bail, Context, Result};
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use tracing::{info, warn};
+
+use crate::ips::{BinaryImage, CrashReport, Frame, Thread};
+use crate::query::{self, Field};
+use crate::signature::Signature;
 
 pub struct CrashDb {
     conn: Connection,
 }
 
+/// One schema change. Steps run in order, each in its own transaction, and
+/// `PRAGMA user_version` records the last one applied. Never edit a step
+/// that has shipped; add a new one.
+struct Migration {
+    version: u32,
+    description: &'static str,
+    run: fn(&CrashDb) -> Result<()>,
+    /// How to tell the step is already in a database from before
+    /// versioning. Steps added since need none.
+    marker: Option<Marker>,
+}
+
+/// A schema object whose presence shows a step was applied.
+enum Marker {
+    Table(&'static str),
+    Column(&'static str, &'static str),
+    NoStatusCheck(&'static str),
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "baseline schema",
+        run: CrashDb::migrate_baseline,
+        marker: Some(Marker::Table("crashes")),
+    },
+    Migration {
+        version: 2,
+        description: "parsed crash report tables",
+        run: CrashDb::migrate_crash_reports,
+        marker: Some(Marker::Table("crash_reports")),
+    },
+    Migration {
+        version: 3,
+        description: "crash groups",
+        run: CrashDb::migrate_crash_groups,
+        marker: Some(Marker::Table("crash_groups")),
+    },
+    Migration {
+        version: 4,
+        description: "group status and fix columns",
+        run: CrashDb::migrate_group_fixes,
+        marker: Some(Marker::Column("crash_groups", "status")),
+    },
+    Migration {
+        version: 5,
+        description: "symbolicated log path",
+        run: CrashDb::migrate_symbolicated_logs,
+        marker: Some(Marker::Column("crashes", "symbolicated_log_path")),
+    },
+    Migration {
+        version: 6,
+        description: "build metadata",
+        run: CrashDb::migrate_builds,
+        marker: Some(Marker::Table("builds")),
+    },
+    Migration {
+        version: 7,
+        description: "sync high-water marks",
+        run: CrashDb::migrate_sync_state,
+        marker: Some(Marker::Table("sync_state")),
+    },
+    Migration {
+        version: 8,
+        description: "remote deletion timestamps",
+        run: CrashDb::migrate_remote_deleted,
+        marker: Some(Marker::Column("crashes", "remote_deleted_at")),
+    },
+    Migration {
+        version: 9,
+        description: "sync run history",
+        run: CrashDb::migrate_sync_runs,
+        marker: Some(Marker::Table("sync_runs")),
+    },
+    Migration {
+        version: 10,
+        description: "status history",
+        run: CrashDb::migrate_status_events,
+        marker: Some(Marker::Table("status_events")),
+    },
+    Migration {
+        version: 11,
+        description: "notes",
+        run: CrashDb::migrate_comments,
+        marker: Some(Marker::Table("comments")),
+    },
+    Migration {
+        version: 12,
+        description: "assignees",
+        run: CrashDb::migrate_assignees,
+        marker: Some(Marker::Column("crashes", "assignee")),
+    },
+    Migration {
+        version: 13,
+        description: "tags",
+        run: CrashDb::migrate_tags,
+        marker: Some(Marker::Table("tags")),
+    },
+    Migration {
+        version: 14,
+        description: "drop the built-in status CHECK",
+        run: CrashDb::migrate_custom_statuses,
+        marker: Some(Marker::NoStatusCheck("crashes")),
+    },
+    Migration {
+        version: 15,
+        description: "full-text search index",
+        run: CrashDb::migrate_search_index,
+        marker: None,
+    },
+];
+
+/// Schema version this build writes.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MigrationStep {
+    pub version: u32,
+    pub description: &'static str,
+}
+
+/// What `CrashDb::migrate` did.
+#[derive(Debug, Serialize)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    pub applied: Vec<MigrationStep>,
+    /// Copy of the database taken before migrating, if it had any tables.
+    pub backup: Option<String>,
+}
+
 // ─── Row types (serializable for JSON output) ────────────────────────────────
 
 #[derive(Debug, Serialize, Clone)]
@@ -39,9 +176,30 @@ pub struct CrashRow {
     pub fixed_at: Option<String>,
     pub fix_notes: Option<String>,
     pub duplicate_of: Option<i64>,
+    pub symbolicated_log_path: Option<String>,
     // Joined from apps table
     pub app_bundle_id: Option<String>,
     pub app_name: Option<String>,
+    // Joined from crash_reports table (parsed .ips log)
+    pub exception_type: Option<String>,
+    pub signal: Option<String>,
+    pub termination_reason: Option<String>,
+    pub regression_of: Option<i64>,
+    // Joined from crash_groups table
+    pub group_id: Option<i64>,
+    /// Short id of the group's signature, as in `GroupRow::hash`.
+    pub group_hash: Option<String>,
+    /// Normalized signature text: exception plus top frames.
+    pub signature: Option<String>,
+    // Joined from builds table, falling back to the parsed log header
+    pub app_version: Option<String>,
+    pub build_number: Option<String>,
+    /// When a full sync last found the submission gone from App Store
+    /// Connect (deleted or expired). The local copy is all that's left.
+    pub remote_deleted_at: Option<String>,
+    pub assignee: Option<String>,
+    /// Labels from `tag`, sorted.
+    pub tags: Vec<String>,
 }
 
 pub struct NewCrash {
@@ -61,10 +219,37 @@ pub struct NewCrash {
     pub connection_type: Option<String>,
 }
 
+/// `list` / `feedback list` options beyond the fixed filters: a `--where`
+/// expression, date range end, build, sort order and paging.
+#[derive(Debug, Default)]
+pub struct ListQuery {
+    pub filter: Option<query::Expr>,
+    /// Inclusive: `2025-06-30` includes all of that day.
+    pub until: Option<String>,
+    /// Build number, app version or ASC build ID.
+    pub build: Option<String>,
+    /// Newest first when empty.
+    pub sort: Vec<query::SortKey>,
+    pub offset: usize,
+}
+
 pub struct CrashFilters {
     pub status: Option<Vec<String>>,
     pub since: Option<String>,
     pub app_bundle_id: Option<String>,
+    pub exception_type: Option<String>,
+    pub group_id: Option<i64>,
+    pub regressions_only: bool,
+    /// Also list crashes no longer on App Store Connect.
+    pub include_expired: bool,
+    /// List only crashes no longer on App Store Connect.
+    pub only_expired: bool,
+    pub assignee: Option<String>,
+    pub unassigned: bool,
+    /// Only items carrying any of these tags (all of them with `all_tags`).
+    pub tags: Vec<String>,
+    pub all_tags: bool,
+    pub query: ListQuery,
     pub limit: usize,
 }
 
@@ -74,9 +259,53 @@ pub struct Stats {
     pub by_status: HashMap<String, i64>,
     pub by_device: Vec<(String, i64)>,
     pub by_os: Vec<(String, i64)>,
+    pub by_exception: Vec<(String, i64)>,
+    pub by_build: Vec<(String, i64)>,
+    /// Open (`[workflow] open`) crashes per assignee.
+    pub by_assignee: Vec<(String, i64)>,
+    pub by_tag: Vec<(String, i64)>,
     pub unfixed: i64,
 }
 
+// ─── Crash group row types ────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GroupRow {
+    pub id: i64,
+    pub app_id: i64,
+    /// Short stable id derived from `signature`.
+    pub hash: String,
+    /// Normalized signature text: exception plus top frames.
+    pub signature: String,
+    pub exception_type: Option<String>,
+    pub frames: Vec<String>,
+    pub created_at: String,
+    pub crash_count: i64,
+    pub unfixed: i64,
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    pub build_count: i64,
+    pub device_count: i64,
+    pub status: String,
+    pub fixed_build_id: Option<String>,
+    pub fixed_at: Option<String>,
+    pub fix_notes: Option<String>,
+    // Joined from apps table
+    pub app_bundle_id: Option<String>,
+}
+
+pub struct GroupFilters {
+    pub app_bundle_id: Option<String>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupBreakdown {
+    pub by_build: Vec<(String, i64)>,
+    pub by_device: Vec<(String, i64)>,
+    pub by_os: Vec<(String, i64)>,
+}
+
 // ─── Feedback row types ───────────────────────────────────────────────────────
 
 #[derive(Debug, Serialize, Clone)]
@@ -105,6 +334,13 @@ pub struct FeedbackRow {
     // Joined from apps table
     pub app_bundle_id: Option<String>,
     pub app_name: Option<String>,
+    // Joined from builds table
+    pub app_version: Option<String>,
+    pub build_number: Option<String>,
+    pub remote_deleted_at: Option<String>,
+    pub assignee: Option<String>,
+    /// Labels from `tag`, sorted.
+    pub tags: Vec<String>,
 }
 
 pub struct NewFeedback {
@@ -126,6 +362,14 @@ pub struct FeedbackFilters {
     pub status: Option<Vec<String>>,
     pub since: Option<String>,
     pub app_bundle_id: Option<String>,
+    pub include_expired: bool,
+    pub only_expired: bool,
+    pub assignee: Option<String>,
+    pub unassigned: bool,
+    /// Only items carrying any of these tags (all of them with `all_tags`).
+    pub tags: Vec<String>,
+    pub all_tags: bool,
+    pub query: ListQuery,
     pub limit: usize,
 }
 
@@ -135,9 +379,173 @@ pub struct FeedbackStats {
     pub by_status: HashMap<String, i64>,
     pub by_device: Vec<(String, i64)>,
     pub by_os: Vec<(String, i64)>,
+    pub by_build: Vec<(String, i64)>,
+    /// Open (`[workflow] open`) feedback per assignee.
+    pub by_assignee: Vec<(String, i64)>,
+    pub by_tag: Vec<(String, i64)>,
     pub unfixed: i64,
 }
 
+// ─── Build row types ──────────────────────────────────────────────────────────
+
+/// A TestFlight build, resolved from the opaque ASC build id that crashes and
+/// feedbacks reference.
+#[derive(Debug, Serialize, Clone)]
+pub struct BuildRow {
+    pub id: String,
+    pub app_id: Option<i64>,
+    /// Marketing version (CFBundleShortVersionString), e.g. "2.3.1".
+    pub version: Option<String>,
+    /// Build number (CFBundleVersion), e.g. "412".
+    pub build_number: Option<String>,
+    pub platform: Option<String>,
+    pub uploaded_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub expired: Option<bool>,
+    pub min_os_version: Option<String>,
+    pub fetched_at: String,
+}
+
+/// Crash and feedback activity for one build, used by `builds report`.
+#[derive(Debug, Serialize, Clone)]
+pub struct BuildActivity {
+    pub build_id: String,
+    pub version: Option<String>,
+    pub build_number: Option<String>,
+    pub uploaded_at: Option<String>,
+    pub first_seen: String,
+    pub crash_count: i64,
+    pub feedback_count: i64,
+    /// Crashes flagged as regressions of a fixed group.
+    pub regression_count: i64,
+    /// Crash groups (signatures) seen in this build.
+    pub group_ids: Vec<i64>,
+}
+
+pub struct NewBuild {
+    pub id: String,
+    pub app_id: i64,
+    pub version: Option<String>,
+    pub build_number: Option<String>,
+    pub platform: Option<String>,
+    pub uploaded_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub expired: Option<bool>,
+    pub min_os_version: Option<String>,
+}
+
+// ─── Sync state ───────────────────────────────────────────────────────────────
+
+/// How far `sync` has got through one app's crashes or feedbacks.
+#[derive(Debug, Serialize, Clone)]
+pub struct SyncState {
+    pub app_id: i64,
+    /// "crashes" or "feedbacks".
+    pub resource: String,
+    /// High-water mark: every submission created at or after this has been
+    /// seen by a sync that walked its pages to completion.
+    pub last_created_at: Option<String>,
+    pub last_full_sweep_at: Option<String>,
+    pub updated_at: String,
+}
+
+/// One status change of a crash or feedback. Append-only.
+#[derive(Debug, Serialize, Clone)]
+pub struct StatusEvent {
+    pub id: i64,
+    /// "crash" or "feedback".
+    pub entity: String,
+    pub entity_id: i64,
+    pub old_status: Option<String>,
+    pub new_status: String,
+    pub notes: Option<String>,
+    pub actor: String,
+    pub created_at: String,
+}
+
+/// A free-form note left on a crash or feedback with `note`.
+#[derive(Debug, Serialize, Clone)]
+pub struct Comment {
+    pub id: i64,
+    /// "crash" or "feedback".
+    pub entity: String,
+    pub entity_id: i64,
+    pub body: String,
+    pub actor: String,
+    pub created_at: String,
+}
+
+/// Options for `CrashDb::search`.
+#[derive(Debug)]
+pub struct SearchFilters {
+    /// Only "crash" or only "feedback" results.
+    pub entity: Option<&'static str>,
+    pub app: Option<String>,
+    /// Pass the query to FTS5 as-is (AND/OR/NOT, NEAR, `prefix*`, `col:term`)
+    /// instead of matching each word literally.
+    pub raw: bool,
+    pub limit: usize,
+}
+
+/// A crash or feedback matched by `search`.
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    /// "crash" or "feedback".
+    pub entity: String,
+    pub id: i64,
+    pub status: String,
+    pub created_at: String,
+    pub app_bundle_id: Option<String>,
+    /// Best-matching passage, with matches in `[brackets]`.
+    pub snippet: String,
+    /// Relevance; higher is better.
+    pub score: f64,
+}
+
+/// What one `sync` run did, accumulated as it goes.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct SyncRunCounts {
+    pub pages: u32,
+    pub new_crashes: usize,
+    pub new_feedbacks: usize,
+    pub logs_downloaded: usize,
+    pub screenshots_downloaded: usize,
+    /// Failed downloads, plus the error that ended the run, if any.
+    pub errors: Vec<String>,
+}
+
+/// A recorded `sync` run. `status` is "running" until it finishes, so a run
+/// that was killed stays "running".
+#[derive(Debug, Serialize, Clone)]
+pub struct SyncRunRow {
+    pub id: i64,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub apps: Vec<String>,
+    pub full: bool,
+    pub status: String,
+    pub exit_code: Option<i32>,
+    #[serde(flatten)]
+    pub counts: SyncRunCounts,
+}
+
+/// Format a version and build number as "2.3.1 (412)", the way TestFlight
+/// shows it.
+pub fn build_label(version: Option<&str>, build_number: Option<&str>) -> Option<String> {
+    match (version, build_number) {
+        (Some(v), Some(b)) => Some(format!("{v} ({b})")),
+        (Some(v), None) => Some(v.to_string()),
+        (None, Some(b)) => Some(format!("({b})")),
+        (None, None) => None,
+    }
+}
+
+/// SQL for a crash/feedback build label; `{t}` is the row alias. Unresolved
+/// builds fall back to the raw ASC id.
+fn build_label_sql(t: &str) -> String {
+    format!("COALESCE(b.version || ' (' || b.build_number || ')', b.version, {t}.build_id)")
+}
+
 // ─── Database implementation ─────────────────────────────────────────────────
 
 const CRASH_SELECT: &str = "
@@ -146,9 +554,74 @@ const CRASH_SELECT: &str = "
            c.tester_email, c.tester_comment, c.bundle_id, c.build_id,
            c.app_uptime_ms, c.battery_pct, c.connection_type,
            c.has_log, c.log_path, c.status, c.fixed_at, c.fix_notes,
-           c.duplicate_of, a.bundle_id, a.name
+           c.duplicate_of, a.bundle_id, a.name,
+           r.exception_type, r.signal, r.termination_reason,
+           c.regression_of, c.group_id, g.hash, c.symbolicated_log_path,
+           COALESCE(b.version, r.app_version),
+           COALESCE(b.build_number, r.build_version), c.remote_deleted_at,
+           c.assignee,
+           (SELECT group_concat(tag, ',') FROM (SELECT tag FROM tags
+             WHERE entity = 'crash' AND entity_id = c.id ORDER BY tag)),
+           g.signature
     FROM crashes c
     JOIN apps a ON a.id = c.app_id
+    LEFT JOIN crash_reports r ON r.crash_id = c.id
+    LEFT JOIN crash_groups g ON g.id = c.group_id
+    LEFT JOIN builds b ON b.id = c.build_id
+";
+
+/// Crash fields for `--where` and `--sort`, named as in `CrashRow`'s JSON.
+pub const CRASH_FIELDS: &[Field] = &[
+    Field::int("id", "c.id"),
+    Field::int("app_id", "c.app_id"),
+    Field::text("submission_id", "c.submission_id"),
+    Field::text("created_at", "c.created_at"),
+    Field::text("synced_at", "c.synced_at"),
+    Field::text("device_model", "c.device_model"),
+    Field::version("os_version", "c.os_version"),
+    Field::text("app_platform", "c.app_platform"),
+    Field::text("architecture", "c.architecture"),
+    Field::text("tester_email", "c.tester_email"),
+    Field::text("tester_comment", "c.tester_comment"),
+    Field::text("bundle_id", "c.bundle_id"),
+    Field::text("build_id", "c.build_id"),
+    Field::int("app_uptime_ms", "c.app_uptime_ms"),
+    Field::int("battery_pct", "c.battery_pct"),
+    Field::text("connection_type", "c.connection_type"),
+    Field::bool("has_log", "c.has_log"),
+    Field::text("log_path", "c.log_path"),
+    Field::text("status", "c.status"),
+    Field::text("fixed_at", "c.fixed_at"),
+    Field::text("fix_notes", "c.fix_notes"),
+    Field::int("duplicate_of", "c.duplicate_of"),
+    Field::text("symbolicated_log_path", "c.symbolicated_log_path"),
+    Field::text("app_bundle_id", "a.bundle_id"),
+    Field::text("app_name", "a.name"),
+    Field::text("exception_type", "r.exception_type"),
+    Field::text("signal", "r.signal"),
+    Field::text("termination_reason", "r.termination_reason"),
+    Field::int("regression_of", "c.regression_of"),
+    Field::int("group_id", "c.group_id"),
+    Field::text("group_hash", "g.hash"),
+    Field::text("signature", "g.signature"),
+    Field::version("app_version", "COALESCE(b.version, r.app_version)"),
+    Field::version("build_number", "COALESCE(b.build_number, r.build_version)"),
+    Field::text("remote_deleted_at", "c.remote_deleted_at"),
+    Field::text("assignee", "c.assignee"),
+    Field::tags("tags", "crash", "c.id"),
+];
+
+const GROUP_SELECT: &str = "
+    SELECT g.id, g.app_id, g.hash, g.exception_type, g.frames, g.created_at,
+           COUNT(c.id),
+           COALESCE(SUM(c.status IN (SELECT status FROM temp.open_statuses)), 0),
+           MIN(c.created_at), MAX(c.created_at),
+           COUNT(DISTINCT c.build_id), COUNT(DISTINCT c.device_model),
+           a.bundle_id, g.status, g.fixed_build_id, g.fixed_at, g.fix_notes,
+           g.signature
+    FROM crash_groups g
+    JOIN apps a ON a.id = g.app_id
+    LEFT JOIN crashes c ON c.group_id = g.id
 ";
 
 const FEEDBACK_SELECT: &str = "
@@ -158,22 +631,210 @@ const FEEDBACK_SELECT: &str = "
            f.battery_pct, f.connection_type,
            f.has_screenshot, f.screenshot_path, f.screenshot_mime_type,
            f.status, f.fixed_at, f.fix_notes,
-           f.duplicate_of, a.bundle_id, a.name
+           f.duplicate_of, a.bundle_id, a.name,
+           b.version, b.build_number, f.remote_deleted_at, f.assignee,
+           (SELECT group_concat(tag, ',') FROM (SELECT tag FROM tags
+             WHERE entity = 'feedback' AND entity_id = f.id ORDER BY tag))
     FROM feedbacks f
     JOIN apps a ON a.id = f.app_id
+    LEFT JOIN builds b ON b.id = f.build_id
 ";
 
+/// Feedback fields for `--where` and `--sort`, named as in `FeedbackRow`'s JSON.
+pub const FEEDBACK_FIELDS: &[Field] = &[
+    Field::int("id", "f.id"),
+    Field::int("app_id", "f.app_id"),
+    Field::text("submission_id", "f.submission_id"),
+    Field::text("created_at", "f.created_at"),
+    Field::text("synced_at", "f.synced_at"),
+    Field::text("device_model", "f.device_model"),
+    Field::version("os_version", "f.os_version"),
+    Field::text("app_platform", "f.app_platform"),
+    Field::text("tester_email", "f.tester_email"),
+    Field::text("tester_comment", "f.tester_comment"),
+    Field::text("bundle_id", "f.bundle_id"),
+    Field::text("build_id", "f.build_id"),
+    Field::int("battery_pct", "f.battery_pct"),
+    Field::text("connection_type", "f.connection_type"),
+    Field::bool("has_screenshot", "f.has_screenshot"),
+    Field::text("screenshot_path", "f.screenshot_path"),
+    Field::text("screenshot_mime_type", "f.screenshot_mime_type"),
+    Field::text("status", "f.status"),
+    Field::text("fixed_at", "f.fixed_at"),
+    Field::text("fix_notes", "f.fix_notes"),
+    Field::int("duplicate_of", "f.duplicate_of"),
+    Field::text("app_bundle_id", "a.bundle_id"),
+    Field::text("app_name", "a.name"),
+    Field::version("app_version", "b.version"),
+    Field::version("build_number", "b.build_number"),
+    Field::text("remote_deleted_at", "f.remote_deleted_at"),
+    Field::text("assignee", "f.assignee"),
+    Field::tags("tags", "feedback", "f.id"),
+];
+
 impl CrashDb {
+    /// Open the database, bringing its schema up to date first.
     pub fn open(path: &Path) -> Result<Self> {
+        let db = Self::open_unmigrated(path)?;
+        let report = db.migrate()?;
+        if let Some(ref backup) = report.backup {
+            info!(
+                from = report.from,
+                to = report.to,
+                backup = backup.as_str(),
+                "migrated database schema"
+            );
+        }
+        // Which statuses count as unresolved comes from `[workflow] open`;
+        // queries read it from this per-connection table.
+        db.conn.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS open_statuses (status TEXT PRIMARY KEY);
+             INSERT OR IGNORE INTO open_statuses VALUES ('new'), ('investigating');",
+        )?;
+        Ok(db)
+    }
+
+    /// Open the database as-is, for `db version` / `db migrate`.
+    pub fn open_unmigrated(path: &Path) -> Result<Self> {
         let conn =
             Connection::open(path).with_context(|| format!("open db: {}", path.display()))?;
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
-        let db = Self { conn };
-        db.migrate()?;
-        Ok(db)
+        conn.create_collation(query::VERSION_COLLATION, query::compare_versions)?;
+        Ok(Self { conn })
+    }
+
+    /// Replace the statuses that count as open (unfixed) for this connection.
+    pub fn set_open_statuses(&self, statuses: &[String]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM temp.open_statuses", [])?;
+        for s in statuses {
+            tx.execute(
+                "INSERT OR IGNORE INTO temp.open_statuses VALUES (?1)",
+                params![s],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ─── Schema migrations ───────────────────────────────────────────────
+
+    /// The schema version recorded in `PRAGMA user_version`; 0 for databases
+    /// from before versioning (or brand new ones).
+    pub fn schema_version(&self) -> Result<u32> {
+        self.conn
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .context("read schema version")
+    }
+
+    /// Steps `migrate` would apply, oldest first.
+    pub fn pending_migrations(&self) -> Result<Vec<MigrationStep>> {
+        let version = self.schema_version()?;
+        if version > SCHEMA_VERSION {
+            bail!(
+                "database schema is v{version}, newer than this build supports (v{SCHEMA_VERSION}); \
+                 upgrade asc-crash-fetcher"
+            );
+        }
+        // An unversioned database re-runs the baseline to fill in anything
+        // missing, then skips the steps it already has.
+        let done = match version {
+            0 => self.unversioned_version()?,
+            v => v,
+        };
+        Ok(MIGRATIONS
+            .iter()
+            .filter(|m| m.version > done || (version == 0 && m.version == 1))
+            .map(|m| MigrationStep {
+                version: m.version,
+                description: m.description,
+            })
+            .collect())
+    }
+
+    /// Apply pending migrations in order. An existing database is first
+    /// copied to `backups/` next to it.
+    pub fn migrate(&self) -> Result<MigrationReport> {
+        let from = self.schema_version()?;
+        let pending = self.pending_migrations()?;
+        let mut report = MigrationReport {
+            from,
+            to: from,
+            applied: Vec::new(),
+            backup: None,
+        };
+        if pending.is_empty() {
+            return Ok(report);
+        }
+
+        let has_tables: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table'",
+            [],
+            |r| r.get(0),
+        )?;
+        if has_tables {
+            report.backup = Some(self.backup(from)?);
+        }
+        let adopted = match from {
+            0 => self.unversioned_version()?,
+            _ => 0,
+        };
+        for step in pending {
+            let m = &MIGRATIONS[step.version as usize - 1];
+            // The baseline step on an unversioned database records every
+            // step it was found to have.
+            let version = m.version.max(adopted);
+            self.run_migration(m, version)
+                .with_context(|| format!("migration v{} ({})", m.version, m.description))?;
+            report.to = version;
+            report.applied.push(step);
+        }
+        Ok(report)
+    }
+
+    /// Copy the database to `backups/{name}-v{version}-{timestamp}.db`.
+    fn backup(&self, version: u32) -> Result<String> {
+        let path = Path::new(self.conn.path().context("database has no file path")?);
+        let dir = path.with_file_name("backups");
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("could not create {}", dir.display()))?;
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("crashes");
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+        let mut dest = dir.join(format!("{stem}-v{version}-{stamp}.db"));
+        let mut n = 1;
+        while dest.exists() {
+            n += 1;
+            dest = dir.join(format!("{stem}-v{version}-{stamp}-{n}.db"));
+        }
+        // VACUUM INTO takes a consistent snapshot, WAL contents included.
+        self.conn
+            .execute("VACUUM INTO ?1", params![dest.to_string_lossy()])
+            .with_context(|| format!("could not back up database to {}", dest.display()))?;
+        Ok(dest.to_string_lossy().into_owned())
+    }
+
+    /// Run one step atomically and record `version`. Foreign keys are off
+    /// for the duration so steps can rebuild tables; the pragma can't change
+    /// inside a transaction.
+    fn run_migration(&self, m: &Migration, version: u32) -> Result<()> {
+        self.conn.execute_batch("PRAGMA foreign_keys=OFF;")?;
+        let result = (|| {
+            let tx = self.conn.unchecked_transaction()?;
+            (m.run)(self)?;
+            tx.pragma_update(None, "user_version", version)?;
+            tx.commit()?;
+            Ok(())
+        })();
+        self.conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+        result
     }
 
-    fn migrate(&self) -> Result<()> {
+    /// v1: the schema as first released. `IF NOT EXISTS` so it also adopts
+    /// databases from before versioning.
+    fn migrate_baseline(&self) -> Result<()> {
         self.conn.execute_batch(
             "
             CREATE TABLE IF NOT EXISTS apps (
@@ -248,6 +909,424 @@ impl CrashDb {
         Ok(())
     }
 
+    /// v2: tables `reparse` fills from `.ips` logs.
+    fn migrate_crash_reports(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE crash_reports (
+                crash_id           INTEGER PRIMARY KEY REFERENCES crashes(id),
+                parsed_at          TEXT NOT NULL DEFAULT (datetime('now')),
+                parse_error        TEXT,
+                app_version        TEXT,
+                build_version      TEXT,
+                exception_type     TEXT,
+                exception_subtype  TEXT,
+                signal             TEXT,
+                termination_reason TEXT,
+                crashed_thread     INTEGER
+            );
+
+            CREATE INDEX idx_crash_reports_exception ON crash_reports(exception_type);
+
+            CREATE TABLE crash_threads (
+                crash_id     INTEGER NOT NULL REFERENCES crashes(id),
+                thread_index INTEGER NOT NULL,
+                name         TEXT,
+                queue        TEXT,
+                triggered    INTEGER DEFAULT 0,
+                PRIMARY KEY (crash_id, thread_index)
+            );
+
+            CREATE TABLE crash_frames (
+                crash_id      INTEGER NOT NULL REFERENCES crashes(id),
+                thread_index  INTEGER NOT NULL,
+                frame_index   INTEGER NOT NULL,
+                image_index   INTEGER,
+                image_name    TEXT,
+                symbol        TEXT,
+                symbol_offset INTEGER,
+                image_offset  INTEGER,
+                source_file   TEXT,
+                source_line   INTEGER,
+                PRIMARY KEY (crash_id, thread_index, frame_index)
+            );
+
+            CREATE TABLE crash_images (
+                crash_id    INTEGER NOT NULL REFERENCES crashes(id),
+                image_index INTEGER NOT NULL,
+                name        TEXT,
+                uuid        TEXT,
+                arch        TEXT,
+                base        INTEGER,
+                size        INTEGER,
+                path        TEXT,
+                PRIMARY KEY (crash_id, image_index)
+            );
+
+            CREATE INDEX idx_crash_images_uuid ON crash_images(uuid);
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v3: signature groups and each crash's group.
+    fn migrate_crash_groups(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE crash_groups (
+                id             INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_id         INTEGER NOT NULL REFERENCES apps(id),
+                signature      TEXT NOT NULL,
+                hash           TEXT NOT NULL,
+                exception_type TEXT,
+                frames         TEXT NOT NULL DEFAULT '',
+                created_at     TEXT NOT NULL DEFAULT (datetime('now')),
+                UNIQUE (app_id, signature)
+            );
+
+            ALTER TABLE crashes ADD COLUMN group_id INTEGER REFERENCES crash_groups(id);
+            CREATE INDEX idx_crashes_group ON crashes(group_id);
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v4: marking a group fixed in a build, and the group a crash regressed.
+    fn migrate_group_fixes(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            ALTER TABLE crashes ADD COLUMN regression_of INTEGER REFERENCES crash_groups(id);
+            ALTER TABLE crash_groups ADD COLUMN status TEXT NOT NULL DEFAULT 'open';
+            ALTER TABLE crash_groups ADD COLUMN fixed_build_id TEXT;
+            ALTER TABLE crash_groups ADD COLUMN fixed_at TEXT;
+            ALTER TABLE crash_groups ADD COLUMN fix_notes TEXT;
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v5: where `symbolicate` writes a crash's symbolicated log.
+    fn migrate_symbolicated_logs(&self) -> Result<()> {
+        self.conn
+            .execute_batch("ALTER TABLE crashes ADD COLUMN symbolicated_log_path TEXT;")?;
+        Ok(())
+    }
+
+    /// v6: build metadata, keyed by the ASC build id crashes/feedbacks
+    /// reference.
+    fn migrate_builds(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE builds (
+                id             TEXT PRIMARY KEY,
+                app_id         INTEGER REFERENCES apps(id),
+                version        TEXT,
+                build_number   TEXT,
+                platform       TEXT,
+                uploaded_at    TEXT,
+                expires_at     TEXT,
+                expired        INTEGER,
+                min_os_version TEXT,
+                fetched_at     TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v7: per-app, per-resource pagination progress for `sync`.
+    fn migrate_sync_state(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE sync_state (
+                app_id             INTEGER NOT NULL REFERENCES apps(id),
+                resource           TEXT NOT NULL,
+                last_created_at    TEXT,
+                last_full_sweep_at TEXT,
+                updated_at         TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (app_id, resource)
+            );
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v8: when a submission was found gone from App Store Connect.
+    fn migrate_remote_deleted(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            ALTER TABLE crashes ADD COLUMN remote_deleted_at TEXT;
+            ALTER TABLE feedbacks ADD COLUMN remote_deleted_at TEXT;
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v9: one row per `sync` invocation.
+    fn migrate_sync_runs(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE sync_runs (
+                id                     INTEGER PRIMARY KEY AUTOINCREMENT,
+                started_at             TEXT NOT NULL DEFAULT (datetime('now')),
+                finished_at            TEXT,
+                apps                   TEXT NOT NULL DEFAULT '',
+                full                   INTEGER NOT NULL DEFAULT 0,
+                status                 TEXT NOT NULL DEFAULT 'running'
+                                       CHECK(status IN ('running','ok','failed')),
+                exit_code              INTEGER,
+                pages                  INTEGER NOT NULL DEFAULT 0,
+                new_crashes            INTEGER NOT NULL DEFAULT 0,
+                new_feedbacks          INTEGER NOT NULL DEFAULT 0,
+                logs_downloaded        INTEGER NOT NULL DEFAULT 0,
+                screenshots_downloaded INTEGER NOT NULL DEFAULT 0,
+                errors                 TEXT NOT NULL DEFAULT '[]'
+            );
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v10: append-only history of crash/feedback status changes.
+    fn migrate_status_events(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE status_events (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity     TEXT NOT NULL CHECK(entity IN ('crash','feedback')),
+                entity_id  INTEGER NOT NULL,
+                old_status TEXT,
+                new_status TEXT NOT NULL,
+                notes      TEXT,
+                actor      TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+
+            CREATE INDEX idx_status_events_entity ON status_events(entity, entity_id);
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v11: free-form notes on crashes/feedback, oldest first.
+    fn migrate_comments(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE comments (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity     TEXT NOT NULL CHECK(entity IN ('crash','feedback')),
+                entity_id  INTEGER NOT NULL,
+                body       TEXT NOT NULL,
+                actor      TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+
+            CREATE INDEX idx_comments_entity ON comments(entity, entity_id);
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v12: who a crash or feedback is assigned to.
+    fn migrate_assignees(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            ALTER TABLE crashes ADD COLUMN assignee TEXT;
+            ALTER TABLE feedbacks ADD COLUMN assignee TEXT;
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v13: labels on crashes/feedback (`tag` / `untag`).
+    fn migrate_tags(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE tags (
+                entity     TEXT NOT NULL CHECK(entity IN ('crash','feedback')),
+                entity_id  INTEGER NOT NULL,
+                tag        TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (entity, entity_id, tag)
+            );
+
+            CREATE INDEX idx_tags_tag ON tags(tag, entity);
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// v14: let `[workflow]` define statuses beyond the five built-in ones.
+    fn migrate_custom_statuses(&self) -> Result<()> {
+        self.drop_status_check("crashes")?;
+        self.drop_status_check("feedbacks")?;
+        Ok(())
+    }
+
+    /// v15: FTS5 index over tester comments, fix notes, notes and crash logs.
+    /// Triggers keep the text columns current; log text is written by
+    /// `index_log`, and backfilled here from logs already on disk.
+    fn migrate_search_index(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "
+            -- One row per crash (rowid = id) or feedback (rowid = -id).
+            CREATE VIRTUAL TABLE search_index USING fts5(
+                entity UNINDEXED,
+                entity_id UNINDEXED,
+                tester_comment,
+                fix_notes,
+                notes,
+                log
+            );
+
+            CREATE TRIGGER search_crash_insert AFTER INSERT ON crashes BEGIN
+                INSERT INTO search_index (rowid, entity, entity_id, tester_comment, fix_notes)
+                VALUES (new.id, 'crash', new.id, new.tester_comment, new.fix_notes);
+            END;
+            CREATE TRIGGER search_crash_update AFTER UPDATE OF tester_comment, fix_notes
+                ON crashes BEGIN
+                UPDATE search_index
+                SET tester_comment = new.tester_comment, fix_notes = new.fix_notes
+                WHERE rowid = new.id;
+            END;
+            CREATE TRIGGER search_crash_delete AFTER DELETE ON crashes BEGIN
+                DELETE FROM search_index WHERE rowid = old.id;
+            END;
+
+            CREATE TRIGGER search_feedback_insert AFTER INSERT ON feedbacks BEGIN
+                INSERT INTO search_index (rowid, entity, entity_id, tester_comment, fix_notes)
+                VALUES (-new.id, 'feedback', new.id, new.tester_comment, new.fix_notes);
+            END;
+            CREATE TRIGGER search_feedback_update AFTER UPDATE OF tester_comment, fix_notes
+                ON feedbacks BEGIN
+                UPDATE search_index
+                SET tester_comment = new.tester_comment, fix_notes = new.fix_notes
+                WHERE rowid = -new.id;
+            END;
+            CREATE TRIGGER search_feedback_delete AFTER DELETE ON feedbacks BEGIN
+                DELETE FROM search_index WHERE rowid = -old.id;
+            END;
+
+            CREATE TRIGGER search_comment_insert AFTER INSERT ON comments BEGIN
+                UPDATE search_index
+                SET notes = (SELECT group_concat(body, char(10)) FROM comments
+                             WHERE entity = new.entity AND entity_id = new.entity_id)
+                WHERE rowid = CASE new.entity WHEN 'crash' THEN new.entity_id
+                                              ELSE -new.entity_id END;
+            END;
+
+            INSERT INTO search_index (rowid, entity, entity_id, tester_comment, fix_notes, notes)
+            SELECT id, 'crash', id, tester_comment, fix_notes,
+                   (SELECT group_concat(body, char(10)) FROM comments m
+                    WHERE m.entity = 'crash' AND m.entity_id = c.id)
+            FROM crashes c;
+
+            INSERT INTO search_index (rowid, entity, entity_id, tester_comment, fix_notes, notes)
+            SELECT -id, 'feedback', id, tester_comment, fix_notes,
+                   (SELECT group_concat(body, char(10)) FROM comments m
+                    WHERE m.entity = 'feedback' AND m.entity_id = f.id)
+            FROM feedbacks f;
+            ",
+        )?;
+
+        let logs: Vec<(i64, String)> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT id, COALESCE(symbolicated_log_path, log_path) FROM crashes
+                 WHERE COALESCE(symbolicated_log_path, log_path) IS NOT NULL",
+            )?;
+            let rows = stmt
+                .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        for (id, path) in logs {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => self.index_log(id, &text)?,
+                Err(e) => warn!(id, path = path.as_str(), err = %e, "could not index crash log"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuild `table` without the CHECK that pinned `status` to the five
+    /// built-in values, so `[workflow]` can define more. No-op once done.
+    fn drop_status_check(&self, table: &str) -> Result<()> {
+        let sql: String = self.conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            params![table],
+            |r| r.get(0),
+        )?;
+        let Some(start) = sql.find("CHECK(status IN") else {
+            return Ok(());
+        };
+        let end = start
+            + sql[start..]
+                .find("))")
+                .context("malformed status CHECK constraint")?
+            + 2;
+        let create = format!("{}{}", &sql[..start], &sql[end..]).replacen(
+            &format!("CREATE TABLE {table}"),
+            &format!("CREATE TABLE {table}_new"),
+            1,
+        );
+        let indexes: Vec<String> = self
+            .conn
+            .prepare(
+                "SELECT sql FROM sqlite_master
+                 WHERE type = 'index' AND tbl_name = ?1 AND sql IS NOT NULL",
+            )?
+            .query_map(params![table], |r| r.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Runs inside `run_migration`, with foreign keys off.
+        self.conn.execute_batch(&format!(
+            "{create};
+             INSERT INTO {table}_new SELECT * FROM {table};
+             DROP TABLE {table};
+             ALTER TABLE {table}_new RENAME TO {table};
+             {};",
+            indexes.join(";\n")
+        ))?;
+        Ok(())
+    }
+
+    /// The last step a database from before versioning already has: those
+    /// builds created tables and added columns in the same order as the
+    /// steps, so the newest marker present tells how far it got.
+    fn unversioned_version(&self) -> Result<u32> {
+        let mut version = 0;
+        for m in MIGRATIONS {
+            if let Some(marker) = &m.marker {
+                if self.has_marker(marker)? {
+                    version = m.version;
+                }
+            }
+        }
+        Ok(version)
+    }
+
+    fn has_marker(&self, marker: &Marker) -> Result<bool> {
+        let found = match marker {
+            Marker::Table(table) => self.conn.query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                params![table],
+                |r| r.get(0),
+            )?,
+            Marker::Column(table, column) => self.conn.query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+                params![table, column],
+                |r| r.get(0),
+            )?,
+            Marker::NoStatusCheck(table) => self.conn.query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master
+                 WHERE type = 'table' AND name = ?1 AND sql NOT LIKE '%CHECK(status IN%'",
+                params![table],
+                |r| r.get(0),
+            )?,
+        };
+        Ok(found)
+    }
+
     // ─── Apps ────────────────────────────────────────────────────────────
 
     /// Insert or update an app, returning its local DB id.
@@ -273,6 +1352,165 @@ impl CrashDb {
         Ok(id)
     }
 
+    /// Look up an app's local DB id by bundle ID.
+    pub fn find_app_id(&self, bundle_id: &str) -> Result<Option<i64>> {
+        self.conn
+            .query_row(
+                "SELECT id FROM apps WHERE bundle_id = ?1",
+                params![bundle_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("find app")
+    }
+
+    // ─── Sync state ──────────────────────────────────────────────────────
+
+    pub fn get_sync_state(&self, app_id: i64, resource: &str) -> Result<Option<SyncState>> {
+        self.conn
+            .query_row(
+                "SELECT app_id, resource, last_created_at, last_full_sweep_at, updated_at
+                 FROM sync_state WHERE app_id = ?1 AND resource = ?2",
+                params![app_id, resource],
+                |row| {
+                    Ok(SyncState {
+                        app_id: row.get(0)?,
+                        resource: row.get(1)?,
+                        last_created_at: row.get(2)?,
+                        last_full_sweep_at: row.get(3)?,
+                        updated_at: row.get(4)?,
+                    })
+                },
+            )
+            .optional()
+            .context("get sync state")
+    }
+
+    /// Record a walk of a resource's pages that left nothing unread. The
+    /// high-water mark only moves forward; `full` also stamps the full-sweep
+    /// time.
+    pub fn record_sweep(
+        &self,
+        app_id: i64,
+        resource: &str,
+        newest_created_at: Option<&str>,
+        full: bool,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO sync_state (app_id, resource, last_created_at, last_full_sweep_at)
+             VALUES (?1, ?2, ?3, CASE WHEN ?4 THEN datetime('now') END)
+             ON CONFLICT(app_id, resource) DO UPDATE SET
+               last_created_at = CASE
+                 WHEN ?3 > COALESCE(last_created_at, '') THEN ?3
+                 ELSE last_created_at END,
+               last_full_sweep_at = CASE
+                 WHEN ?4 THEN datetime('now')
+                 ELSE last_full_sweep_at END,
+               updated_at = datetime('now')",
+            params![app_id, resource, newest_created_at, full],
+        )?;
+        Ok(())
+    }
+
+    /// After a complete walk of an app's "crashes" or "feedbacks", mark rows
+    /// the API didn't return as deleted remotely, and clear the mark on rows
+    /// that came back. Returns (newly marked, restored).
+    pub fn reconcile_remote(
+        &self,
+        app_id: i64,
+        resource: &str,
+        seen: &HashSet<String>,
+    ) -> Result<(usize, usize)> {
+        let table = match resource {
+            "crashes" | "feedbacks" => resource,
+            other => bail!("unknown sync resource '{other}'"),
+        };
+        let known: Vec<(i64, String, bool)> = self
+            .conn
+            .prepare(&format!(
+                "SELECT id, submission_id, remote_deleted_at IS NOT NULL
+                 FROM {table} WHERE app_id = ?1"
+            ))?
+            .query_map(params![app_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        let (mut gone, mut restored) = (0, 0);
+        for (id, submission_id, deleted) in known {
+            match (seen.contains(&submission_id), deleted) {
+                (false, false) => {
+                    tx.execute(
+                        &format!(
+                            "UPDATE {table} SET remote_deleted_at = datetime('now') WHERE id = ?1"
+                        ),
+                        params![id],
+                    )?;
+                    gone += 1;
+                }
+                (true, true) => {
+                    tx.execute(
+                        &format!("UPDATE {table} SET remote_deleted_at = NULL WHERE id = ?1"),
+                        params![id],
+                    )?;
+                    restored += 1;
+                }
+                _ => {}
+            }
+        }
+        tx.commit()?;
+        Ok((gone, restored))
+    }
+
+    // ─── Sync runs ───────────────────────────────────────────────────────
+
+    /// Record the start of a sync run, returning its id.
+    pub fn start_sync_run(&self, apps: &[&str], full: bool) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO sync_runs (apps, full) VALUES (?1, ?2)",
+            params![apps.join(","), full],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    pub fn finish_sync_run(&self, id: i64, counts: &SyncRunCounts, exit_code: i32) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sync_runs SET
+               finished_at = datetime('now'),
+               status = ?2, exit_code = ?3, pages = ?4,
+               new_crashes = ?5, new_feedbacks = ?6,
+               logs_downloaded = ?7, screenshots_downloaded = ?8, errors = ?9
+             WHERE id = ?1",
+            params![
+                id,
+                if exit_code == 0 { "ok" } else { "failed" },
+                exit_code,
+                counts.pages,
+                counts.new_crashes as i64,
+                counts.new_feedbacks as i64,
+                counts.logs_downloaded as i64,
+                counts.screenshots_downloaded as i64,
+                serde_json::to_string(&counts.errors)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent sync runs first.
+    pub fn list_sync_runs(&self, limit: usize) -> Result<Vec<SyncRunRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, started_at, finished_at, apps, full, status, exit_code, pages,
+                    new_crashes, new_feedbacks, logs_downloaded, screenshots_downloaded,
+                    errors
+             FROM sync_runs ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![limit as i64], row_to_sync_run)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
     // ─── Crashes ─────────────────────────────────────────────────────────
 
     /// Insert a new crash. Returns the local id, or None if it already exists.
@@ -335,28 +1573,524 @@ impl CrashDb {
             }
         }
 
-        if let Some(ref since) = f.since {
-            conditions.push(format!("c.created_at >= ?{idx}"));
-            bind_values.push(Box::new(since.clone()));
-            idx += 1;
-        }
+        if let Some(ref since) = f.since {
+            conditions.push(format!("c.created_at >= ?{idx}"));
+            bind_values.push(Box::new(since.clone()));
+            idx += 1;
+        }
+
+        if let Some(ref bundle) = f.app_bundle_id {
+            conditions.push(format!("a.bundle_id = ?{idx}"));
+            bind_values.push(Box::new(bundle.clone()));
+            idx += 1;
+        }
+
+        if let Some(ref exception) = f.exception_type {
+            conditions.push(format!("r.exception_type = ?{idx}"));
+            bind_values.push(Box::new(exception.clone()));
+            idx += 1;
+        }
+
+        if let Some(group_id) = f.group_id {
+            conditions.push(format!("c.group_id = ?{idx}"));
+            bind_values.push(Box::new(group_id));
+            idx += 1;
+        }
+
+        if f.regressions_only {
+            conditions.push("c.regression_of IS NOT NULL".to_string());
+        }
+
+        if f.only_expired {
+            conditions.push("c.remote_deleted_at IS NOT NULL".to_string());
+        } else if !f.include_expired {
+            conditions.push("c.remote_deleted_at IS NULL".to_string());
+        }
+
+        if let Some(ref assignee) = f.assignee {
+            conditions.push(format!("c.assignee = ?{idx}"));
+            bind_values.push(Box::new(assignee.clone()));
+            idx += 1;
+        }
+
+        if f.unassigned {
+            conditions.push("c.assignee IS NULL".to_string());
+        }
+
+        if !f.tags.is_empty() {
+            conditions.push(tag_condition(
+                "crash",
+                "c",
+                &f.tags,
+                f.all_tags,
+                &mut idx,
+                &mut bind_values,
+            ));
+        }
+
+        let order = list_query_sql(
+            &f.query,
+            "c",
+            CRASH_FIELDS,
+            &mut conditions,
+            &mut idx,
+            &mut bind_values,
+        )?;
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        let sql = format!(
+            "{CRASH_SELECT}{where_clause} ORDER BY {order} LIMIT ?{idx} OFFSET ?{}",
+            idx + 1
+        );
+        bind_values.push(Box::new(f.limit as i64));
+        bind_values.push(Box::new(f.query.offset as i64));
+
+        let params_ref: Vec<&dyn rusqlite::types::ToSql> =
+            bind_values.iter().map(|b| b.as_ref()).collect();
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params_ref.as_slice(), row_to_crash)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Return crashes that don't yet have a downloaded log.
+    pub fn crashes_missing_logs(&self) -> Result<Vec<CrashRow>> {
+        let sql = format!(
+            "{CRASH_SELECT} WHERE c.has_log = 0 AND c.remote_deleted_at IS NULL
+             ORDER BY c.created_at DESC"
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], row_to_crash)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    pub fn set_log(&self, id: i64, log_path: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE crashes SET has_log = 1, log_path = ?1 WHERE id = ?2",
+            params![log_path, id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_symbolicated_log(&self, id: i64, path: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE crashes SET symbolicated_log_path = ?1 WHERE id = ?2",
+            params![path, id],
+        )?;
+        Ok(())
+    }
+
+    // ─── Parsed crash reports ────────────────────────────────────────────
+
+    /// Store a parsed `.ips` report, replacing any previous parse.
+    pub fn save_report(&self, crash_id: i64, report: &CrashReport) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        delete_report(&tx, crash_id)?;
+        tx.execute(
+            "INSERT INTO crash_reports
+             (crash_id, app_version, build_version, exception_type, exception_subtype,
+              signal, termination_reason, crashed_thread)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8)",
+            params![
+                crash_id,
+                report.app_version,
+                report.build_version,
+                report.exception_type,
+                report.exception_subtype,
+                report.signal,
+                report.termination_reason,
+                report.crashed_thread,
+            ],
+        )?;
+
+        {
+            let mut thread_stmt = tx.prepare(
+                "INSERT INTO crash_threads (crash_id, thread_index, name, queue, triggered)
+                 VALUES (?1,?2,?3,?4,?5)",
+            )?;
+            let mut frame_stmt = tx.prepare(
+                "INSERT INTO crash_frames
+                 (crash_id, thread_index, frame_index, image_index, image_name, symbol,
+                  symbol_offset, image_offset, source_file, source_line)
+                 VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10)",
+            )?;
+            for t in &report.threads {
+                thread_stmt.execute(params![crash_id, t.index, t.name, t.queue, t.triggered])?;
+                for f in &t.frames {
+                    frame_stmt.execute(params![
+                        crash_id,
+                        t.index,
+                        f.index,
+                        f.image_index,
+                        f.image_name,
+                        f.symbol,
+                        f.symbol_offset,
+                        f.image_offset,
+                        f.source_file,
+                        f.source_line,
+                    ])?;
+                }
+            }
+
+            let mut image_stmt = tx.prepare(
+                "INSERT INTO crash_images
+                 (crash_id, image_index, name, uuid, arch, base, size, path)
+                 VALUES (?1,?2,?3,?4,?5,?6,?7,?8)",
+            )?;
+            for img in &report.images {
+                image_stmt.execute(params![
+                    crash_id, img.index, img.name, img.uuid, img.arch, img.base, img.size,
+                    img.path,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Record that a log could not be parsed, so sync doesn't retry it forever.
+    pub fn save_report_error(&self, crash_id: i64, error: &str) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        delete_report(&tx, crash_id)?;
+        tx.execute(
+            "INSERT INTO crash_reports (crash_id, parse_error) VALUES (?1, ?2)",
+            params![crash_id, error],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Load the parsed report for a crash. Returns None if it was never parsed
+    /// or parsing failed.
+    pub fn get_report(&self, crash_id: i64) -> Result<Option<CrashReport>> {
+        let header = self
+            .conn
+            .query_row(
+                "SELECT app_version, build_version, exception_type, exception_subtype,
+                        signal, termination_reason, crashed_thread
+                 FROM crash_reports WHERE crash_id = ?1 AND parse_error IS NULL",
+                params![crash_id],
+                |row| {
+                    Ok(CrashReport {
+                        app_version: row.get(0)?,
+                        build_version: row.get(1)?,
+                        exception_type: row.get(2)?,
+                        exception_subtype: row.get(3)?,
+                        signal: row.get(4)?,
+                        termination_reason: row.get(5)?,
+                        crashed_thread: row.get(6)?,
+                        threads: Vec::new(),
+                        images: Vec::new(),
+                    })
+                },
+            )
+            .optional()?;
+        let Some(mut report) = header else {
+            return Ok(None);
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT thread_index, name, queue, triggered FROM crash_threads
+             WHERE crash_id = ?1 ORDER BY thread_index",
+        )?;
+        report.threads = stmt
+            .query_map(params![crash_id], |row| {
+                Ok(Thread {
+                    index: row.get(0)?,
+                    name: row.get(1)?,
+                    queue: row.get(2)?,
+                    triggered: row.get::<_, i32>(3)? != 0,
+                    frames: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT thread_index, frame_index, image_index, image_name, symbol,
+                    symbol_offset, image_offset, source_file, source_line
+             FROM crash_frames WHERE crash_id = ?1 ORDER BY thread_index, frame_index",
+        )?;
+        let frames = stmt.query_map(params![crash_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                Frame {
+                    index: row.get(1)?,
+                    image_index: row.get(2)?,
+                    image_name: row.get(3)?,
+                    symbol: row.get(4)?,
+                    symbol_offset: row.get(5)?,
+                    image_offset: row.get(6)?,
+                    source_file: row.get(7)?,
+                    source_line: row.get(8)?,
+                },
+            ))
+        })?;
+        for r in frames {
+            let (thread_index, frame) = r?;
+            if let Some(t) = report.threads.iter_mut().find(|t| t.index == thread_index) {
+                t.frames.push(frame);
+            }
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT image_index, name, uuid, arch, base, size, path
+             FROM crash_images WHERE crash_id = ?1 ORDER BY image_index",
+        )?;
+        report.images = stmt
+            .query_map(params![crash_id], |row| {
+                Ok(BinaryImage {
+                    index: row.get(0)?,
+                    name: row.get(1)?,
+                    uuid: row.get(2)?,
+                    arch: row.get(3)?,
+                    base: row.get(4)?,
+                    size: row.get(5)?,
+                    path: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(report))
+    }
+
+    /// Return crashes with a downloaded log that has not been parsed yet.
+    pub fn crashes_missing_reports(&self) -> Result<Vec<CrashRow>> {
+        let sql = format!(
+            "{CRASH_SELECT} WHERE c.has_log = 1 AND r.crash_id IS NULL ORDER BY c.created_at DESC"
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], row_to_crash)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Return all crashes with a downloaded log.
+    pub fn crashes_with_logs(&self) -> Result<Vec<CrashRow>> {
+        let sql = format!("{CRASH_SELECT} WHERE c.has_log = 1 ORDER BY c.id");
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], row_to_crash)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Return parsed crashes that have not been assigned to a group yet.
+    pub fn crashes_missing_groups(&self) -> Result<Vec<CrashRow>> {
+        let sql = format!(
+            "{CRASH_SELECT} WHERE c.group_id IS NULL AND r.crash_id IS NOT NULL \
+             AND r.parse_error IS NULL ORDER BY c.id"
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], row_to_crash)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    // ─── Builds ──────────────────────────────────────────────────────────
+
+    pub fn upsert_build(&self, b: &NewBuild) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO builds (id, app_id, version, build_number, platform,
+                                 uploaded_at, expires_at, expired, min_os_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(id) DO UPDATE SET
+               app_id = ?2, version = ?3, build_number = ?4, platform = ?5,
+               uploaded_at = ?6, expires_at = ?7, expired = ?8,
+               min_os_version = ?9, fetched_at = datetime('now')",
+            params![
+                b.id,
+                b.app_id,
+                b.version,
+                b.build_number,
+                b.platform,
+                b.uploaded_at,
+                b.expires_at,
+                b.expired,
+                b.min_os_version,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_build(&self, id: &str) -> Result<Option<BuildRow>> {
+        self.conn
+            .query_row(
+                &format!("{BUILD_SELECT} WHERE id = ?1"),
+                params![id],
+                row_to_build,
+            )
+            .optional()
+            .context("get build")
+    }
+
+    /// Build ids referenced by an app's crashes or feedbacks that have no
+    /// metadata yet.
+    pub fn builds_missing(&self, app_id: i64) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT build_id FROM crashes WHERE app_id = ?1 AND build_id IS NOT NULL
+             UNION
+             SELECT build_id FROM feedbacks WHERE app_id = ?1 AND build_id IS NOT NULL
+             EXCEPT
+             SELECT id FROM builds",
+        )?;
+        let rows = stmt
+            .query_map(params![app_id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Per-build activity for an app, oldest build first. Builds are ordered
+    /// by upload date, falling back to the first crash/feedback seen.
+    pub fn build_activity(&self, app_id: i64) -> Result<Vec<BuildActivity>> {
+        let mut stmt = self.conn.prepare(
+            "WITH seen AS (
+                 SELECT build_id, created_at FROM crashes
+                 WHERE app_id = ?1 AND build_id IS NOT NULL
+                 UNION ALL
+                 SELECT build_id, created_at FROM feedbacks
+                 WHERE app_id = ?1 AND build_id IS NOT NULL
+             )
+             SELECT s.build_id, b.version, b.build_number, b.uploaded_at,
+                    MIN(s.created_at),
+                    (SELECT COUNT(*) FROM crashes c
+                     WHERE c.app_id = ?1 AND c.build_id = s.build_id),
+                    (SELECT COUNT(*) FROM feedbacks f
+                     WHERE f.app_id = ?1 AND f.build_id = s.build_id),
+                    (SELECT COUNT(*) FROM crashes c
+                     WHERE c.app_id = ?1 AND c.build_id = s.build_id
+                       AND c.regression_of IS NOT NULL)
+             FROM seen s
+             LEFT JOIN builds b ON b.id = s.build_id
+             GROUP BY s.build_id
+             ORDER BY COALESCE(b.uploaded_at, MIN(s.created_at)), s.build_id",
+        )?;
+        let mut builds = stmt
+            .query_map(params![app_id], |row| {
+                Ok(BuildActivity {
+                    build_id: row.get(0)?,
+                    version: row.get(1)?,
+                    build_number: row.get(2)?,
+                    uploaded_at: row.get(3)?,
+                    first_seen: row.get(4)?,
+                    crash_count: row.get(5)?,
+                    feedback_count: row.get(6)?,
+                    regression_count: row.get(7)?,
+                    group_ids: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT build_id, group_id FROM crashes
+             WHERE app_id = ?1 AND build_id IS NOT NULL AND group_id IS NOT NULL
+             ORDER BY group_id",
+        )?;
+        let pairs = stmt
+            .query_map(params![app_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (build_id, group_id) in pairs {
+            if let Some(b) = builds.iter_mut().find(|b| b.build_id == build_id) {
+                b.group_ids.push(group_id);
+            }
+        }
+        Ok(builds)
+    }
+
+    // ─── Crash groups ────────────────────────────────────────────────────
+
+    /// Find or create the group for a signature, returning its id.
+    pub fn upsert_group(&self, app_id: i64, sig: &Signature) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO crash_groups (app_id, signature, hash, exception_type, frames)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(app_id, signature) DO NOTHING",
+            params![
+                app_id,
+                sig.text,
+                sig.hash,
+                sig.exception_type,
+                sig.frames.join("\n")
+            ],
+        )?;
+        let id: i64 = self.conn.query_row(
+            "SELECT id FROM crash_groups WHERE app_id = ?1 AND signature = ?2",
+            params![app_id, sig.text],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    pub fn set_crash_group(&self, crash_id: i64, group_id: Option<i64>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE crashes SET group_id = ?1 WHERE id = ?2",
+            params![group_id, crash_id],
+        )?;
+        Ok(())
+    }
+
+    /// Give group `to` the triage state of `from` when its crashes move
+    /// there (e.g. symbolication changed their signature), unless `to` has
+    /// been triaged itself.
+    pub fn carry_group_state(&self, from: i64, to: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE crash_groups
+             SET (status, fixed_build_id, fixed_at, fix_notes) =
+                 (SELECT status, fixed_build_id, fixed_at, fix_notes
+                  FROM crash_groups WHERE id = ?1)
+             WHERE id = ?2 AND status = 'open' AND fix_notes IS NULL
+               AND EXISTS (SELECT 1 FROM crash_groups
+                           WHERE id = ?1 AND (status != 'open' OR fix_notes IS NOT NULL))",
+            params![from, to],
+        )?;
+        Ok(())
+    }
 
-        if let Some(ref bundle) = f.app_bundle_id {
-            conditions.push(format!("a.bundle_id = ?{idx}"));
-            bind_values.push(Box::new(bundle.clone()));
-            idx += 1;
-        }
+    /// Delete untriaged groups that no longer have any crashes (e.g. after
+    /// regrouping). Fixed ones stay, so late crashes with the old signature
+    /// are still recognised.
+    pub fn prune_empty_groups(&self) -> Result<usize> {
+        let n = self.conn.execute(
+            "DELETE FROM crash_groups
+             WHERE status = 'open' AND fix_notes IS NULL
+               AND NOT EXISTS (SELECT 1 FROM crashes c WHERE c.group_id = crash_groups.id)",
+            [],
+        )?;
+        Ok(n)
+    }
 
-        let _ = idx; // suppress unused warning
+    pub fn get_group(&self, id: i64) -> Result<Option<GroupRow>> {
+        let sql = format!("{GROUP_SELECT} WHERE g.id = ?1 GROUP BY g.id");
+        self.conn
+            .query_row(&sql, params![id], row_to_group)
+            .optional()
+            .context("get group")
+    }
 
-        let where_clause = if conditions.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", conditions.join(" AND "))
+    pub fn list_groups(&self, f: &GroupFilters) -> Result<Vec<GroupRow>> {
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        let where_clause = match f.app_bundle_id {
+            Some(ref bundle) => {
+                bind_values.push(Box::new(bundle.clone()));
+                " WHERE a.bundle_id = ?1"
+            }
+            None => "",
         };
-
         let sql = format!(
-            "{CRASH_SELECT}{where_clause} ORDER BY c.created_at DESC LIMIT ?{}",
+            "{GROUP_SELECT}{where_clause} GROUP BY g.id \
+             ORDER BY COUNT(c.id) DESC, MAX(c.created_at) DESC LIMIT ?{}",
             bind_values.len() + 1
         );
         bind_values.push(Box::new(f.limit as i64));
@@ -365,60 +2099,443 @@ impl CrashDb {
             bind_values.iter().map(|b| b.as_ref()).collect();
         let mut stmt = self.conn.prepare(&sql)?;
         let rows = stmt
-            .query_map(params_ref.as_slice(), row_to_crash)?
+            .query_map(params_ref.as_slice(), row_to_group)?
             .collect::<Result<Vec<_>, _>>()?;
         Ok(rows)
     }
 
-    /// Return crashes that don't yet have a downloaded log.
-    pub fn crashes_missing_logs(&self) -> Result<Vec<CrashRow>> {
-        let sql = format!("{CRASH_SELECT} WHERE c.has_log = 0 ORDER BY c.created_at DESC");
-        let mut stmt = self.conn.prepare(&sql)?;
-        let rows = stmt
-            .query_map([], row_to_crash)?
+    /// Mark a group fixed in `build_id`, closing its open crashes.
+    /// Returns the number of crashes closed, or None if the group doesn't exist.
+    pub fn fix_group(
+        &self,
+        id: i64,
+        build_id: &str,
+        notes: Option<&str>,
+        actor: &str,
+    ) -> Result<Option<usize>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        let affected = tx.execute(
+            "UPDATE crash_groups SET status = 'fixed', fixed_build_id = ?1, fixed_at = ?2,
+             fix_notes = COALESCE(?3, fix_notes) WHERE id = ?4",
+            params![build_id, now, notes, id],
+        )?;
+        if affected == 0 {
+            return Ok(None);
+        }
+        let open: Vec<(i64, String)> = tx
+            .prepare(
+                "SELECT id, status FROM crashes
+                 WHERE group_id = ?1 AND status IN (SELECT status FROM temp.open_statuses)",
+            )?
+            .query_map(params![id], |row| Ok((row.get(0)?, row.get(1)?)))?
             .collect::<Result<Vec<_>, _>>()?;
-        Ok(rows)
+        tx.execute(
+            "UPDATE crashes SET status = 'fixed', fixed_at = COALESCE(fixed_at, ?1),
+             fix_notes = COALESCE(?2, fix_notes)
+             WHERE group_id = ?3 AND status IN (SELECT status FROM temp.open_statuses)",
+            params![now, notes, id],
+        )?;
+        let event_notes = match notes {
+            Some(n) => format!("group #{id} fixed in build {build_id}: {n}"),
+            None => format!("group #{id} fixed in build {build_id}"),
+        };
+        for (crash_id, old) in &open {
+            record_status_event(
+                &tx,
+                "crash",
+                *crash_id,
+                Some(old),
+                "fixed",
+                Some(&event_notes),
+                actor,
+            )?;
+        }
+        tx.commit()?;
+        Ok(Some(open.len()))
     }
 
-    pub fn set_log(&self, id: i64, log_path: &str) -> Result<()> {
+    pub fn set_group_status(&self, id: i64, status: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE crash_groups SET status = ?1 WHERE id = ?2",
+            params![status, id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    pub fn reopen_group(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE crash_groups SET status = 'open', fixed_build_id = NULL, fixed_at = NULL
+             WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    /// The earliest crash in a group that isn't itself a duplicate — the one
+    /// late reports get linked to.
+    pub fn group_primary_crash(&self, group_id: i64, exclude: i64) -> Result<Option<i64>> {
+        self.conn
+            .query_row(
+                "SELECT id FROM crashes
+                 WHERE group_id = ?1 AND id != ?2 AND status != 'duplicate'
+                 ORDER BY created_at, id LIMIT 1",
+                params![group_id, exclude],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("group primary crash")
+    }
+
+    pub fn mark_regression(&self, id: i64, group_id: i64) -> Result<()> {
         self.conn.execute(
-            "UPDATE crashes SET has_log = 1, log_path = ?1 WHERE id = ?2",
-            params![log_path, id],
+            "UPDATE crashes SET regression_of = ?1 WHERE id = ?2",
+            params![group_id, id],
         )?;
         Ok(())
     }
 
-    pub fn update_status(&self, id: i64, status: &str, notes: Option<&str>) -> Result<bool> {
+    /// Order two builds by upload date. Builds whose metadata hasn't been
+    /// fetched fall back to when they were first seen in a crash or feedback.
+    /// Returns None if either build is unknown.
+    pub fn compare_builds(&self, a: &str, b: &str) -> Result<Option<Ordering>> {
+        if a == b {
+            return Ok(Some(Ordering::Equal));
+        }
+        let uploaded = |build_id: &str| -> Result<Option<String>> {
+            Ok(self.get_build(build_id)?.and_then(|b| b.uploaded_at))
+        };
+        if let (Some(x), Some(y)) = (uploaded(a)?, uploaded(b)?) {
+            return Ok(Some(x.cmp(&y)));
+        }
+        match (self.build_first_seen(a)?, self.build_first_seen(b)?) {
+            (Some(x), Some(y)) => Ok(Some(x.cmp(&y))),
+            _ => Ok(None),
+        }
+    }
+
+    /// When the first crash or feedback from a build was created, if any.
+    pub fn build_first_seen(&self, build_id: &str) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT MIN(created_at) FROM (
+                     SELECT created_at FROM crashes WHERE build_id = ?1
+                     UNION ALL
+                     SELECT created_at FROM feedbacks WHERE build_id = ?1
+                 )",
+                params![build_id],
+                |row| row.get(0),
+            )
+            .context("build first seen")
+    }
+
+    /// Affected builds, devices and OS versions for a group.
+    pub fn group_breakdown(&self, id: i64) -> Result<GroupBreakdown> {
+        let count_by = |column: &str| -> Result<Vec<(String, i64)>> {
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT {column}, COUNT(*) FROM crashes \
+                 WHERE group_id = ?1 AND {column} IS NOT NULL \
+                 GROUP BY {column} ORDER BY COUNT(*) DESC"
+            ))?;
+            let rows = stmt
+                .query_map(params![id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(rows)
+        };
+        let by_build = {
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT {label}, COUNT(*) FROM crashes c \
+                 LEFT JOIN builds b ON b.id = c.build_id \
+                 WHERE c.group_id = ?1 AND c.build_id IS NOT NULL \
+                 GROUP BY c.build_id ORDER BY COUNT(*) DESC",
+                label = build_label_sql("c")
+            ))?;
+            let rows = stmt
+                .query_map(params![id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        Ok(GroupBreakdown {
+            by_build,
+            by_device: count_by("device_model")?,
+            by_os: count_by("os_version")?,
+        })
+    }
+
+    pub fn update_status(
+        &self,
+        id: i64,
+        status: &str,
+        notes: Option<&str>,
+        actor: &str,
+    ) -> Result<bool> {
         let fixed_at = if status == "fixed" {
             Some(chrono::Utc::now().to_rfc3339())
         } else {
             None
         };
-        let affected = self.conn.execute(
+        let tx = self.conn.unchecked_transaction()?;
+        let Some((old, _)) = current_status(&tx, "crashes", id)? else {
+            return Ok(false);
+        };
+        tx.execute(
             "UPDATE crashes SET status = ?1, fix_notes = COALESCE(?2, fix_notes),
              fixed_at = COALESCE(?3, fixed_at) WHERE id = ?4",
             params![status, notes, fixed_at, id],
         )?;
-        Ok(affected > 0)
+        record_status_event(&tx, "crash", id, Some(&old), status, notes, actor)?;
+        tx.commit()?;
+        Ok(true)
     }
 
-    pub fn mark_duplicate(&self, id: i64, of_id: i64) -> Result<bool> {
-        let affected = self.conn.execute(
+    pub fn mark_duplicate(
+        &self,
+        id: i64,
+        of_id: i64,
+        notes: Option<&str>,
+        actor: &str,
+    ) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let Some((old, _)) = current_status(&tx, "crashes", id)? else {
+            return Ok(false);
+        };
+        tx.execute(
             "UPDATE crashes SET status = 'duplicate', duplicate_of = ?1 WHERE id = ?2",
             params![of_id, id],
         )?;
-        Ok(affected > 0)
+        let notes = match notes {
+            Some(n) => format!("duplicate of #{of_id}: {n}"),
+            None => format!("duplicate of #{of_id}"),
+        };
+        record_status_event(
+            &tx,
+            "crash",
+            id,
+            Some(&old),
+            "duplicate",
+            Some(&notes),
+            actor,
+        )?;
+        tx.commit()?;
+        Ok(true)
     }
 
-    pub fn reopen(&self, id: i64) -> Result<bool> {
-        let affected = self.conn.execute(
+    /// Reset to "new". The fix notes being cleared are kept in the event.
+    pub fn reopen(&self, id: i64, actor: &str) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let Some((old, old_notes)) = current_status(&tx, "crashes", id)? else {
+            return Ok(false);
+        };
+        tx.execute(
             "UPDATE crashes SET status = 'new', fixed_at = NULL, fix_notes = NULL, \
              duplicate_of = NULL WHERE id = ?1",
             params![id],
         )?;
+        let notes = old_notes.map(|n| format!("cleared notes: {n}"));
+        record_status_event(&tx, "crash", id, Some(&old), "new", notes.as_deref(), actor)?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Set (or with None, clear) who a crash ("crash") or feedback
+    /// ("feedback") is assigned to. Returns false if it doesn't exist.
+    pub fn set_assignee(&self, entity: &str, id: i64, assignee: Option<&str>) -> Result<bool> {
+        let affected = self.conn.execute(
+            &format!(
+                "UPDATE {} SET assignee = ?1 WHERE id = ?2",
+                entity_table(entity)
+            ),
+            params![assignee, id],
+        )?;
         Ok(affected > 0)
     }
 
+    /// Add tags to a crash ("crash") or feedback ("feedback"); ones it already
+    /// has are left alone. Returns false if it doesn't exist.
+    pub fn add_tags(&self, entity: &str, id: i64, tags: &[String]) -> Result<bool> {
+        if current_status(&self.conn, entity_table(entity), id)?.is_none() {
+            return Ok(false);
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        for tag in tags {
+            tx.execute(
+                "INSERT OR IGNORE INTO tags (entity, entity_id, tag, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![entity, id, tag, now],
+            )?;
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Remove tags from a crash or feedback. Returns false if it doesn't exist.
+    pub fn remove_tags(&self, entity: &str, id: i64, tags: &[String]) -> Result<bool> {
+        if current_status(&self.conn, entity_table(entity), id)?.is_none() {
+            return Ok(false);
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        for tag in tags {
+            tx.execute(
+                "DELETE FROM tags WHERE entity = ?1 AND entity_id = ?2 AND tag = ?3",
+                params![entity, id, tag],
+            )?;
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Append a note to a crash ("crash") or feedback ("feedback"). Returns
+    /// None if it doesn't exist.
+    pub fn add_comment(
+        &self,
+        entity: &str,
+        id: i64,
+        body: &str,
+        actor: &str,
+    ) -> Result<Option<Comment>> {
+        if current_status(&self.conn, entity_table(entity), id)?.is_none() {
+            return Ok(None);
+        }
+        let created_at = chrono::Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO comments (entity, entity_id, body, actor, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![entity, id, body, actor, created_at],
+        )?;
+        Ok(Some(Comment {
+            id: self.conn.last_insert_rowid(),
+            entity: entity.to_string(),
+            entity_id: id,
+            body: body.to_string(),
+            actor: actor.to_string(),
+            created_at,
+        }))
+    }
+
+    /// Notes on a crash or feedback, oldest first.
+    pub fn comments(&self, entity: &str, id: i64) -> Result<Vec<Comment>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, entity, entity_id, body, actor, created_at
+             FROM comments WHERE entity = ?1 AND entity_id = ?2 ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map(params![entity, id], |row| {
+                Ok(Comment {
+                    id: row.get(0)?,
+                    entity: row.get(1)?,
+                    entity_id: row.get(2)?,
+                    body: row.get(3)?,
+                    actor: row.get(4)?,
+                    created_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    // ─── Full-text search ────────────────────────────────────────────────
+
+    /// Make a crash log's text searchable, replacing what was indexed before.
+    pub fn index_log(&self, crash_id: i64, text: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE search_index SET log = ?1 WHERE rowid = ?2",
+            params![text, crash_id],
+        )?;
+        Ok(())
+    }
+
+    /// Crashes and feedback matching `query`, best match first.
+    pub fn search(&self, query: &str, filters: &SearchFilters) -> Result<Vec<SearchHit>> {
+        let query = if filters.raw {
+            query.trim().to_string()
+        } else {
+            fts_phrases(query)
+        };
+        if query.is_empty() {
+            bail!("search query is empty");
+        }
+
+        let mut sql = String::from(
+            "SELECT search_index.entity, search_index.entity_id,
+                    COALESCE(c.status, f.status), COALESCE(c.created_at, f.created_at),
+                    COALESCE(ca.bundle_id, fa.bundle_id),
+                    snippet(search_index, -1, '[', ']', '…', 12),
+                    -bm25(search_index)
+             FROM search_index
+             LEFT JOIN crashes c ON search_index.rowid > 0 AND c.id = search_index.rowid
+             LEFT JOIN apps ca ON ca.id = c.app_id
+             LEFT JOIN feedbacks f ON search_index.rowid < 0 AND f.id = -search_index.rowid
+             LEFT JOIN apps fa ON fa.id = f.app_id
+             WHERE search_index MATCH ?1",
+        );
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(query)];
+        let mut idx = 2;
+
+        if let Some(entity) = filters.entity {
+            sql.push_str(&format!(" AND search_index.entity = ?{idx}"));
+            bind_values.push(Box::new(entity));
+            idx += 1;
+        }
+        if let Some(ref app) = filters.app {
+            sql.push_str(&format!(
+                " AND COALESCE(ca.bundle_id, fa.bundle_id) = ?{idx}"
+            ));
+            bind_values.push(Box::new(app.clone()));
+            idx += 1;
+        }
+        sql.push_str(&format!(" ORDER BY bm25(search_index) LIMIT ?{idx}"));
+        bind_values.push(Box::new(filters.limit as i64));
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params: Vec<&dyn rusqlite::types::ToSql> =
+            bind_values.iter().map(|b| b.as_ref()).collect();
+        let rows = stmt
+            .query_map(params.as_slice(), |row| {
+                let snippet: String = row.get(5)?;
+                Ok(SearchHit {
+                    entity: row.get(0)?,
+                    id: row.get(1)?,
+                    status: row.get(2)?,
+                    created_at: row.get(3)?,
+                    app_bundle_id: row.get(4)?,
+                    snippet: snippet.split_whitespace().collect::<Vec<_>>().join(" "),
+                    score: row.get(6)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .context("invalid search query")?;
+        Ok(rows)
+    }
+
+    /// Status history of a crash ("crash") or feedback ("feedback"), oldest
+    /// first.
+    pub fn status_events(&self, entity: &str, id: i64) -> Result<Vec<StatusEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, entity, entity_id, old_status, new_status, notes, actor, created_at
+             FROM status_events WHERE entity = ?1 AND entity_id = ?2 ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map(params![entity, id], |row| {
+                Ok(StatusEvent {
+                    id: row.get(0)?,
+                    entity: row.get(1)?,
+                    entity_id: row.get(2)?,
+                    old_status: row.get(3)?,
+                    new_status: row.get(4)?,
+                    notes: row.get(5)?,
+                    actor: row.get(6)?,
+                    created_at: row.get(7)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
     pub fn stats(&self, app_bundle_id: Option<&str>) -> Result<Stats> {
         let filter = if let Some(b) = app_bundle_id {
             format!(" WHERE a.bundle_id = '{}'", b.replace('\'', "''"))
@@ -447,10 +2564,15 @@ impl CrashDb {
             }
         }
 
-        let unfixed = total
-            - by_status.get("fixed").copied().unwrap_or(0)
-            - by_status.get("wontfix").copied().unwrap_or(0)
-            - by_status.get("duplicate").copied().unwrap_or(0);
+        let unfixed: i64 = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM crashes c JOIN apps a ON a.id = c.app_id{filter}{and} \
+                 c.status IN (SELECT status FROM temp.open_statuses)",
+                and = if filter.is_empty() { " WHERE" } else { " AND" }
+            ),
+            [],
+            |r| r.get(0),
+        )?;
 
         let by_device = self.top_n_group(&format!(
             "SELECT c.device_model, COUNT(*) FROM crashes c \
@@ -466,11 +2588,46 @@ impl CrashDb {
              GROUP BY c.os_version ORDER BY COUNT(*) DESC LIMIT 15"
         ))?;
 
+        let by_exception = self.top_n_group(&format!(
+            "SELECT r.exception_type, COUNT(*) FROM crashes c \
+             JOIN apps a ON a.id = c.app_id \
+             JOIN crash_reports r ON r.crash_id = c.id \
+             AND r.exception_type IS NOT NULL{filter} \
+             GROUP BY r.exception_type ORDER BY COUNT(*) DESC LIMIT 15"
+        ))?;
+
+        let by_build = self.top_n_group(&format!(
+            "SELECT {label}, COUNT(*) FROM crashes c \
+             JOIN apps a ON a.id = c.app_id \
+             LEFT JOIN builds b ON b.id = c.build_id{filter} \
+             GROUP BY c.build_id HAVING c.build_id IS NOT NULL \
+             ORDER BY COUNT(*) DESC LIMIT 15",
+            label = build_label_sql("c")
+        ))?;
+
+        let by_assignee = self.top_n_group(&format!(
+            "SELECT COALESCE(c.assignee, '(unassigned)'), COUNT(*) FROM crashes c \
+             JOIN apps a ON a.id = c.app_id{filter}{and} c.status IN (SELECT status FROM temp.open_statuses) \
+             GROUP BY c.assignee ORDER BY COUNT(*) DESC, c.assignee IS NULL, c.assignee",
+            and = if filter.is_empty() { " WHERE" } else { " AND" }
+        ))?;
+
+        let by_tag = self.top_n_group(&format!(
+            "SELECT t.tag, COUNT(*) FROM tags t \
+             JOIN crashes c ON c.id = t.entity_id AND t.entity = 'crash' \
+             JOIN apps a ON a.id = c.app_id{filter} \
+             GROUP BY t.tag ORDER BY COUNT(*) DESC, t.tag"
+        ))?;
+
         Ok(Stats {
             total,
             by_status,
             by_device,
             by_os,
+            by_exception,
+            by_build,
+            by_assignee,
+            by_tag,
             unfixed,
         })
     }
@@ -488,7 +2645,8 @@ impl CrashDb {
     pub fn count_unfixed(&self) -> Result<i64> {
         self.conn
             .query_row(
-                "SELECT COUNT(*) FROM crashes WHERE status IN ('new','investigating')",
+                "SELECT COUNT(*) FROM crashes
+                 WHERE status IN (SELECT status FROM temp.open_statuses)",
                 [],
                 |r| r.get(0),
             )
@@ -573,7 +2731,41 @@ impl CrashDb {
             idx += 1;
         }
 
-        let _ = idx; // suppress unused warning
+        if f.only_expired {
+            conditions.push("f.remote_deleted_at IS NOT NULL".to_string());
+        } else if !f.include_expired {
+            conditions.push("f.remote_deleted_at IS NULL".to_string());
+        }
+
+        if let Some(ref assignee) = f.assignee {
+            conditions.push(format!("f.assignee = ?{idx}"));
+            bind_values.push(Box::new(assignee.clone()));
+            idx += 1;
+        }
+
+        if f.unassigned {
+            conditions.push("f.assignee IS NULL".to_string());
+        }
+
+        if !f.tags.is_empty() {
+            conditions.push(tag_condition(
+                "feedback",
+                "f",
+                &f.tags,
+                f.all_tags,
+                &mut idx,
+                &mut bind_values,
+            ));
+        }
+
+        let order = list_query_sql(
+            &f.query,
+            "f",
+            FEEDBACK_FIELDS,
+            &mut conditions,
+            &mut idx,
+            &mut bind_values,
+        )?;
 
         let where_clause = if conditions.is_empty() {
             String::new()
@@ -582,10 +2774,11 @@ impl CrashDb {
         };
 
         let sql = format!(
-            "{FEEDBACK_SELECT}{where_clause} ORDER BY f.created_at DESC LIMIT ?{}",
-            bind_values.len() + 1
+            "{FEEDBACK_SELECT}{where_clause} ORDER BY {order} LIMIT ?{idx} OFFSET ?{}",
+            idx + 1
         );
         bind_values.push(Box::new(f.limit as i64));
+        bind_values.push(Box::new(f.query.offset as i64));
 
         let params_ref: Vec<&dyn rusqlite::types::ToSql> =
             bind_values.iter().map(|b| b.as_ref()).collect();
@@ -598,8 +2791,10 @@ impl CrashDb {
 
     /// Return feedbacks that don't yet have a downloaded screenshot.
     pub fn feedbacks_missing_screenshots(&self) -> Result<Vec<FeedbackRow>> {
-        let sql =
-            format!("{FEEDBACK_SELECT} WHERE f.has_screenshot = 0 ORDER BY f.created_at DESC");
+        let sql = format!(
+            "{FEEDBACK_SELECT} WHERE f.has_screenshot = 0 AND f.remote_deleted_at IS NULL
+                 ORDER BY f.created_at DESC"
+        );
         let mut stmt = self.conn.prepare(&sql)?;
         let rows = stmt
             .query_map([], row_to_feedback)?
@@ -620,39 +2815,77 @@ impl CrashDb {
         id: i64,
         status: &str,
         notes: Option<&str>,
+        actor: &str,
     ) -> Result<bool> {
         let fixed_at = if status == "fixed" {
             Some(chrono::Utc::now().to_rfc3339())
         } else {
             None
         };
-        let affected = self.conn.execute(
+        let tx = self.conn.unchecked_transaction()?;
+        let Some((old, _)) = current_status(&tx, "feedbacks", id)? else {
+            return Ok(false);
+        };
+        tx.execute(
             "UPDATE feedbacks SET status = ?1, fix_notes = COALESCE(?2, fix_notes),
              fixed_at = COALESCE(?3, fixed_at) WHERE id = ?4",
             params![status, notes, fixed_at, id],
         )?;
-        Ok(affected > 0)
+        record_status_event(&tx, "feedback", id, Some(&old), status, notes, actor)?;
+        tx.commit()?;
+        Ok(true)
     }
 
-    pub fn mark_feedback_duplicate(&self, id: i64, of_id: i64) -> Result<bool> {
+    pub fn mark_feedback_duplicate(&self, id: i64, of_id: i64, actor: &str) -> Result<bool> {
         // Verify target exists
         if self.get_feedback(of_id)?.is_none() {
             bail!("target feedback #{of_id} not found");
         }
-        let affected = self.conn.execute(
+        let tx = self.conn.unchecked_transaction()?;
+        let Some((old, _)) = current_status(&tx, "feedbacks", id)? else {
+            return Ok(false);
+        };
+        tx.execute(
             "UPDATE feedbacks SET status = 'duplicate', duplicate_of = ?1 WHERE id = ?2",
             params![of_id, id],
         )?;
-        Ok(affected > 0)
+        let notes = format!("duplicate of #{of_id}");
+        record_status_event(
+            &tx,
+            "feedback",
+            id,
+            Some(&old),
+            "duplicate",
+            Some(&notes),
+            actor,
+        )?;
+        tx.commit()?;
+        Ok(true)
     }
 
-    pub fn reopen_feedback(&self, id: i64) -> Result<bool> {
-        let affected = self.conn.execute(
+    /// Reset to "new". The fix notes being cleared are kept in the event.
+    pub fn reopen_feedback(&self, id: i64, actor: &str) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let Some((old, old_notes)) = current_status(&tx, "feedbacks", id)? else {
+            return Ok(false);
+        };
+        tx.execute(
             "UPDATE feedbacks SET status = 'new', fixed_at = NULL, fix_notes = NULL, \
              duplicate_of = NULL WHERE id = ?1",
             params![id],
         )?;
-        Ok(affected > 0)
+        let notes = old_notes.map(|n| format!("cleared notes: {n}"));
+        record_status_event(
+            &tx,
+            "feedback",
+            id,
+            Some(&old),
+            "new",
+            notes.as_deref(),
+            actor,
+        )?;
+        tx.commit()?;
+        Ok(true)
     }
 
     pub fn feedback_stats(&self, app_bundle_id: Option<&str>) -> Result<FeedbackStats> {
@@ -683,10 +2916,15 @@ impl CrashDb {
             }
         }
 
-        let unfixed = total
-            - by_status.get("fixed").copied().unwrap_or(0)
-            - by_status.get("wontfix").copied().unwrap_or(0)
-            - by_status.get("duplicate").copied().unwrap_or(0);
+        let unfixed: i64 = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM feedbacks f JOIN apps a ON a.id = f.app_id{filter}{and} \
+                 f.status IN (SELECT status FROM temp.open_statuses)",
+                and = if filter.is_empty() { " WHERE" } else { " AND" }
+            ),
+            [],
+            |r| r.get(0),
+        )?;
 
         let by_device = self.top_n_group(&format!(
             "SELECT f.device_model, COUNT(*) FROM feedbacks f \
@@ -702,11 +2940,37 @@ impl CrashDb {
              GROUP BY f.os_version ORDER BY COUNT(*) DESC LIMIT 15"
         ))?;
 
+        let by_build = self.top_n_group(&format!(
+            "SELECT {label}, COUNT(*) FROM feedbacks f \
+             JOIN apps a ON a.id = f.app_id \
+             LEFT JOIN builds b ON b.id = f.build_id{filter} \
+             GROUP BY f.build_id HAVING f.build_id IS NOT NULL \
+             ORDER BY COUNT(*) DESC LIMIT 15",
+            label = build_label_sql("f")
+        ))?;
+
+        let by_assignee = self.top_n_group(&format!(
+            "SELECT COALESCE(f.assignee, '(unassigned)'), COUNT(*) FROM feedbacks f \
+             JOIN apps a ON a.id = f.app_id{filter}{and} f.status IN (SELECT status FROM temp.open_statuses) \
+             GROUP BY f.assignee ORDER BY COUNT(*) DESC, f.assignee IS NULL, f.assignee",
+            and = if filter.is_empty() { " WHERE" } else { " AND" }
+        ))?;
+
+        let by_tag = self.top_n_group(&format!(
+            "SELECT t.tag, COUNT(*) FROM tags t \
+             JOIN feedbacks f ON f.id = t.entity_id AND t.entity = 'feedback' \
+             JOIN apps a ON a.id = f.app_id{filter} \
+             GROUP BY t.tag ORDER BY COUNT(*) DESC, t.tag"
+        ))?;
+
         Ok(FeedbackStats {
             total,
             by_status,
             by_device,
             by_os,
+            by_build,
+            by_assignee,
+            by_tag,
             unfixed,
         })
     }
@@ -714,7 +2978,8 @@ impl CrashDb {
     pub fn count_unfixed_feedbacks(&self) -> Result<i64> {
         self.conn
             .query_row(
-                "SELECT COUNT(*) FROM feedbacks WHERE status IN ('new','investigating')",
+                "SELECT COUNT(*) FROM feedbacks
+                 WHERE status IN (SELECT status FROM temp.open_statuses)",
                 [],
                 |r| r.get(0),
             )
@@ -728,6 +2993,176 @@ impl CrashDb {
     }
 }
 
+/// Add the `ListQuery` conditions for rows of `alias` described by `fields`,
+/// returning the `ORDER BY` list.
+fn list_query_sql(
+    q: &ListQuery,
+    alias: &str,
+    fields: &[Field],
+    conditions: &mut Vec<String>,
+    idx: &mut usize,
+    bind_values: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+) -> Result<String> {
+    let sql_of = |name: &str| {
+        fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| f.sql)
+            .expect("field table lists every build column")
+    };
+
+    if let Some(ref until) = q.until {
+        // Compare at the precision given, so a bare date covers the whole day.
+        conditions.push(format!(
+            "substr({alias}.created_at, 1, length(?{idx})) <= ?{idx}"
+        ));
+        bind_values.push(Box::new(until.clone()));
+        *idx += 1;
+    }
+
+    if let Some(ref build) = q.build {
+        conditions.push(format!(
+            "({} = ?{idx} OR {} = ?{idx} OR {} = ?{idx})",
+            sql_of("build_number"),
+            sql_of("app_version"),
+            sql_of("build_id"),
+        ));
+        bind_values.push(Box::new(build.clone()));
+        *idx += 1;
+    }
+
+    if let Some(ref filter) = q.filter {
+        conditions.push(
+            query::to_sql(filter, fields, idx, bind_values).context("invalid --where filter")?,
+        );
+    }
+
+    if q.sort.is_empty() {
+        Ok(format!("{alias}.created_at DESC, {alias}.id DESC"))
+    } else {
+        let order = query::order_by(&q.sort, fields).context("invalid --sort")?;
+        // Ties break by id so --offset pages don't overlap.
+        Ok(format!("{order}, {alias}.id"))
+    }
+}
+
+/// Turn free text into an FTS5 query that matches every word literally,
+/// so `-[NSObject foo]` or `com.example.app` aren't read as query syntax.
+/// A trailing `*` still makes a word a prefix match.
+fn fts_phrases(text: &str) -> String {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(w) => (w, "*"),
+                None => (word, ""),
+            };
+            (!word.is_empty()).then(|| format!("\"{}\"{prefix}", word.replace('"', "\"\"")))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Table holding an entity: "crash" → crashes, "feedback" → feedbacks.
+fn entity_table(entity: &str) -> &'static str {
+    if entity == "crash" {
+        "crashes"
+    } else {
+        "feedbacks"
+    }
+}
+
+/// `WHERE` condition matching rows of `alias` with any (or all) of `tags`.
+fn tag_condition(
+    entity: &str,
+    alias: &str,
+    tags: &[String],
+    all: bool,
+    idx: &mut usize,
+    bind_values: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+) -> String {
+    let placeholders: Vec<String> = tags
+        .iter()
+        .map(|t| {
+            let p = format!("?{idx}");
+            *idx += 1;
+            bind_values.push(Box::new(t.clone()));
+            p
+        })
+        .collect();
+    let matching = format!(
+        "SELECT COUNT(DISTINCT t.tag) FROM tags t WHERE t.entity = '{entity}' \
+         AND t.entity_id = {alias}.id AND t.tag IN ({})",
+        placeholders.join(",")
+    );
+    if all {
+        format!("({matching}) = {}", tags.len())
+    } else {
+        format!("({matching}) > 0")
+    }
+}
+
+fn split_tags(joined: Option<String>) -> Vec<String> {
+    joined
+        .map(|s| s.split(',').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Status and fix notes of a crash or feedback row.
+fn current_status(
+    conn: &Connection,
+    table: &str,
+    id: i64,
+) -> Result<Option<(String, Option<String>)>> {
+    conn.query_row(
+        &format!("SELECT status, fix_notes FROM {table} WHERE id = ?1"),
+        params![id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+    .context("get status")
+}
+
+fn record_status_event(
+    conn: &Connection,
+    entity: &str,
+    entity_id: i64,
+    old_status: Option<&str>,
+    new_status: &str,
+    notes: Option<&str>,
+    actor: &str,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO status_events
+         (entity, entity_id, old_status, new_status, notes, actor, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            entity,
+            entity_id,
+            old_status,
+            new_status,
+            notes,
+            actor,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )?;
+    Ok(())
+}
+
+fn delete_report(conn: &Connection, crash_id: i64) -> Result<()> {
+    for table in [
+        "crash_frames",
+        "crash_threads",
+        "crash_images",
+        "crash_reports",
+    ] {
+        conn.execute(
+            &format!("DELETE FROM {table} WHERE crash_id = ?1"),
+            params![crash_id],
+        )?;
+    }
+    Ok(())
+}
+
 fn row_to_crash(row: &rusqlite::Row) -> rusqlite::Result<CrashRow> {
     Ok(CrashRow {
         id: row.get(0)?,
@@ -754,6 +3189,47 @@ fn row_to_crash(row: &rusqlite::Row) -> rusqlite::Result<CrashRow> {
         duplicate_of: row.get(21)?,
         app_bundle_id: row.get(22)?,
         app_name: row.get(23)?,
+        exception_type: row.get(24)?,
+        signal: row.get(25)?,
+        termination_reason: row.get(26)?,
+        regression_of: row.get(27)?,
+        group_id: row.get(28)?,
+        group_hash: row.get(29)?,
+        signature: row.get(36)?,
+        symbolicated_log_path: row.get(30)?,
+        app_version: row.get(31)?,
+        build_number: row.get(32)?,
+        remote_deleted_at: row.get(33)?,
+        assignee: row.get(34)?,
+        tags: split_tags(row.get(35)?),
+    })
+}
+
+fn row_to_group(row: &rusqlite::Row) -> rusqlite::Result<GroupRow> {
+    let frames: String = row.get(4)?;
+    Ok(GroupRow {
+        id: row.get(0)?,
+        app_id: row.get(1)?,
+        hash: row.get(2)?,
+        signature: row.get(17)?,
+        exception_type: row.get(3)?,
+        frames: frames
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect(),
+        created_at: row.get(5)?,
+        crash_count: row.get(6)?,
+        unfixed: row.get(7)?,
+        first_seen: row.get(8)?,
+        last_seen: row.get(9)?,
+        build_count: row.get(10)?,
+        device_count: row.get(11)?,
+        app_bundle_id: row.get(12)?,
+        status: row.get(13)?,
+        fixed_build_id: row.get(14)?,
+        fixed_at: row.get(15)?,
+        fix_notes: row.get(16)?,
     })
 }
 
@@ -782,5 +3258,57 @@ fn row_to_feedback(row: &rusqlite::Row) -> rusqlite::Result<FeedbackRow> {
         duplicate_of: row.get(20)?,
         app_bundle_id: row.get(21)?,
         app_name: row.get(22)?,
+        app_version: row.get(23)?,
+        build_number: row.get(24)?,
+        remote_deleted_at: row.get(25)?,
+        assignee: row.get(26)?,
+        tags: split_tags(row.get(27)?),
+    })
+}
+
+const BUILD_SELECT: &str = "
+    SELECT id, app_id, version, build_number, platform, uploaded_at,
+           expires_at, expired, min_os_version, fetched_at
+    FROM builds
+";
+
+fn row_to_build(row: &rusqlite::Row) -> rusqlite::Result<BuildRow> {
+    Ok(BuildRow {
+        id: row.get(0)?,
+        app_id: row.get(1)?,
+        version: row.get(2)?,
+        build_number: row.get(3)?,
+        platform: row.get(4)?,
+        uploaded_at: row.get(5)?,
+        expires_at: row.get(6)?,
+        expired: row.get::<_, Option<i32>>(7)?.map(|v| v != 0),
+        min_os_version: row.get(8)?,
+        fetched_at: row.get(9)?,
+    })
+}
+
+fn row_to_sync_run(row: &rusqlite::Row) -> rusqlite::Result<SyncRunRow> {
+    let apps: String = row.get(3)?;
+    let errors: String = row.get(12)?;
+    Ok(SyncRunRow {
+        id: row.get(0)?,
+        started_at: row.get(1)?,
+        finished_at: row.get(2)?,
+        apps: apps
+            .split(',')
+            .filter(|a| !a.is_empty())
+            .map(String::from)
+            .collect(),
+        full: row.get::<_, i32>(4)? != 0,
+        status: row.get(5)?,
+        exit_code: row.get(6)?,
+        counts: SyncRunCounts {
+            pages: row.get(7)?,
+            new_crashes: row.get::<_, i64>(8)? as usize,
+            new_feedbacks: row.get::<_, i64>(9)? as usize,
+            logs_downloaded: row.get::<_, i64>(10)? as usize,
+            screenshots_downloaded: row.get::<_, i64>(11)? as usize,
+            errors: serde_json::from_str(&errors).unwrap_or_default(),
+        },
     })
 }
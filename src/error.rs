@@ -0,0 +1,213 @@
+//! Typed App Store Connect errors and the CLI exit codes they map to.
+//!
+//! `AscClient` returns these (wrapped in `anyhow::Error`) for every failed
+//! request; `main` finds them in the error chain to pick an exit code, so
+//! scripts can branch on the failure class instead of parsing stderr.
+
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::types::{ApiError, ErrorResponse};
+
+/// Stable process exit codes. Clap uses 2 for usage errors.
+pub mod exit {
+    pub const GENERAL: i32 = 1;
+    pub const AUTH: i32 = 3;
+    pub const FORBIDDEN: i32 = 4;
+    pub const NOT_FOUND: i32 = 5;
+    pub const RATE_LIMITED: i32 = 6;
+    pub const SERVER: i32 = 7;
+    pub const NETWORK: i32 = 8;
+    pub const API: i32 = 9;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AscError {
+    #[error(
+        "App Store Connect rejected the API key ({}). Check issuer_id, key_id and \
+         private_key in config.toml, and that the key hasn't been revoked.",
+        summary(.errors, "401")
+    )]
+    Unauthorized { errors: Vec<ApiError> },
+
+    #[error(
+        "the API key isn't allowed to do this ({}). TestFlight feedback needs a key \
+         with the App Manager role or higher.",
+        summary(.errors, "403")
+    )]
+    Forbidden { errors: Vec<ApiError> },
+
+    #[error("not found ({})", summary(.errors, "404"))]
+    NotFound { errors: Vec<ApiError> },
+
+    #[error(
+        "app '{bundle_id}' not found in App Store Connect. Check the bundle_id in \
+         config.toml and that the API key can see the app (`asc-crash-fetcher apps`)."
+    )]
+    AppNotFound { bundle_id: String },
+
+    #[error(
+        "App Store Connect rate limit exceeded ({}). Apple allows a fixed number of \
+         requests per hour; try again later.",
+        summary(.errors, "429")
+    )]
+    RateLimited {
+        errors: Vec<ApiError>,
+        retry_after: Option<Duration>,
+    },
+
+    #[error("App Store Connect server error ({}). Try again later.", summary(.errors, .status.as_str()))]
+    Server {
+        status: StatusCode,
+        errors: Vec<ApiError>,
+    },
+
+    #[error("App Store Connect API error ({})", summary(.errors, .status.as_str()))]
+    Api {
+        status: StatusCode,
+        errors: Vec<ApiError>,
+    },
+
+    #[error("could not reach App Store Connect: {0}")]
+    Network(#[source] reqwest::Error),
+
+    #[error("unexpected response from App Store Connect: {0}")]
+    Decode(#[source] reqwest::Error),
+}
+
+impl AscError {
+    /// Classify a non-2xx response, decoding Apple's `errors[]` body when
+    /// there is one.
+    pub fn from_response(status: StatusCode, retry_after: Option<Duration>, body: &str) -> Self {
+        let errors = match serde_json::from_str::<ErrorResponse>(body) {
+            Ok(r) => r.errors,
+            Err(_) if body.trim().is_empty() => Vec::new(),
+            // Not JSON:API (e.g. a proxy error page); keep the text.
+            Err(_) => vec![ApiError {
+                id: None,
+                status: Some(status.as_str().to_string()),
+                code: None,
+                title: None,
+                detail: Some(body.trim().chars().take(500).collect()),
+                source: None,
+            }],
+        };
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized { errors },
+            StatusCode::FORBIDDEN => Self::Forbidden { errors },
+            StatusCode::NOT_FOUND => Self::NotFound { errors },
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                errors,
+                retry_after,
+            },
+            s if s.is_server_error() => Self::Server { status, errors },
+            _ => Self::Api { status, errors },
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Unauthorized { .. } => exit::AUTH,
+            Self::Forbidden { .. } => exit::FORBIDDEN,
+            Self::NotFound { .. } | Self::AppNotFound { .. } => exit::NOT_FOUND,
+            Self::RateLimited { .. } => exit::RATE_LIMITED,
+            Self::Server { .. } => exit::SERVER,
+            Self::Network(_) => exit::NETWORK,
+            Self::Api { .. } | Self::Decode(_) => exit::API,
+        }
+    }
+
+    /// Short machine-readable class name, used in JSON error output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Unauthorized { .. } => "unauthorized",
+            Self::Forbidden { .. } => "forbidden",
+            Self::NotFound { .. } | Self::AppNotFound { .. } => "not_found",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::Server { .. } => "server_error",
+            Self::Network(_) => "network",
+            Self::Api { .. } => "api_error",
+            Self::Decode(_) => "decode_error",
+        }
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Unauthorized { .. } => Some(StatusCode::UNAUTHORIZED),
+            Self::Forbidden { .. } => Some(StatusCode::FORBIDDEN),
+            Self::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::Server { status, .. } | Self::Api { status, .. } => Some(*status),
+            Self::AppNotFound { .. } | Self::Network(_) | Self::Decode(_) => None,
+        }
+    }
+
+    pub fn errors(&self) -> &[ApiError] {
+        match self {
+            Self::Unauthorized { errors }
+            | Self::Forbidden { errors }
+            | Self::NotFound { errors }
+            | Self::RateLimited { errors, .. }
+            | Self::Server { errors, .. }
+            | Self::Api { errors, .. } => errors,
+            Self::AppNotFound { .. } | Self::Network(_) | Self::Decode(_) => &[],
+        }
+    }
+}
+
+/// JSON form of a failed command, printed to stderr with `--format json`.
+#[derive(Debug, Serialize)]
+pub struct ErrorReport<'a> {
+    pub kind: &'a str,
+    pub exit_code: i32,
+    pub status: Option<u16>,
+    pub message: String,
+    pub errors: &'a [ApiError],
+}
+
+/// Exit code for any error: the class of the first `AscError` in the chain,
+/// or `GENERAL`.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    find(err).map_or(exit::GENERAL, AscError::exit_code)
+}
+
+pub fn find(err: &anyhow::Error) -> Option<&AscError> {
+    err.chain().find_map(|e| e.downcast_ref::<AscError>())
+}
+
+pub fn report(err: &anyhow::Error) -> ErrorReport<'_> {
+    let asc = find(err);
+    ErrorReport {
+        kind: asc.map_or("error", AscError::kind),
+        exit_code: exit_code(err),
+        status: asc.and_then(AscError::status).map(|s| s.as_u16()),
+        message: format!("{err:#}"),
+        errors: asc.map_or(&[], AscError::errors),
+    }
+}
+
+/// "401 NOT_AUTHORIZED: Authentication credentials are missing or invalid."
+fn summary(errors: &[ApiError], status: &str) -> String {
+    let Some(e) = errors.first() else {
+        return status.to_string();
+    };
+    let mut out = e.status.clone().unwrap_or_else(|| status.to_string());
+    if let Some(ref code) = e.code {
+        out.push(' ');
+        out.push_str(code);
+    }
+    if let Some(text) = e.detail.as_deref().or(e.title.as_deref()) {
+        out.push_str(": ");
+        out.push_str(text);
+    }
+    if let Some(src) = e.source.as_ref() {
+        if let Some(p) = src.pointer.as_deref().or(src.parameter.as_deref()) {
+            out.push_str(&format!(" [{p}]"));
+        }
+    }
+    if errors.len() > 1 {
+        out.push_str(&format!(" (+{} more)", errors.len() - 1));
+    }
+    out
+}
@@ -0,0 +1,232 @@
+//! Parser for Apple's `.ips` crash report format (iOS 15+ / macOS 12+).
+//!
+//! An `.ips` file is two JSON documents separated by a newline: a one-line
+//! metadata header followed by the crash body. We only pull out the fields
+//! needed for triage — exception, termination, thread backtraces and the
+//! binary images list.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Structured view of a crash report.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CrashReport {
+    pub app_version: Option<String>,
+    pub build_version: Option<String>,
+    pub exception_type: Option<String>,
+    pub exception_subtype: Option<String>,
+    pub signal: Option<String>,
+    pub termination_reason: Option<String>,
+    pub crashed_thread: Option<i64>,
+    pub threads: Vec<Thread>,
+    pub images: Vec<BinaryImage>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Thread {
+    pub index: i64,
+    pub name: Option<String>,
+    pub queue: Option<String>,
+    pub triggered: bool,
+    pub frames: Vec<Frame>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Frame {
+    pub index: i64,
+    pub image_index: Option<i64>,
+    pub image_name: Option<String>,
+    pub symbol: Option<String>,
+    pub symbol_offset: Option<i64>,
+    pub image_offset: Option<i64>,
+    pub source_file: Option<String>,
+    pub source_line: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BinaryImage {
+    pub index: i64,
+    pub name: Option<String>,
+    pub uuid: Option<String>,
+    pub arch: Option<String>,
+    pub base: Option<i64>,
+    pub size: Option<i64>,
+    pub path: Option<String>,
+}
+
+impl CrashReport {
+    /// The thread that triggered the crash, if known.
+    pub fn crashed(&self) -> Option<&Thread> {
+        match self.crashed_thread {
+            Some(idx) => self.threads.iter().find(|t| t.index == idx),
+            None => self.threads.iter().find(|t| t.triggered),
+        }
+    }
+}
+
+// ─── Raw IPS JSON ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Default)]
+struct RawHeader {
+    app_version: Option<String>,
+    build_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct RawBody {
+    exception: Option<RawException>,
+    termination: Option<RawTermination>,
+    faulting_thread: Option<i64>,
+    #[serde(default)]
+    threads: Vec<RawThread>,
+    #[serde(default)]
+    used_images: Vec<RawImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawException {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    subtype: Option<String>,
+    signal: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawTermination {
+    namespace: Option<String>,
+    code: Option<i64>,
+    indicator: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawThread {
+    #[serde(default)]
+    triggered: bool,
+    name: Option<String>,
+    queue: Option<String>,
+    #[serde(default)]
+    frames: Vec<RawFrame>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFrame {
+    image_index: Option<i64>,
+    image_offset: Option<u64>,
+    symbol: Option<String>,
+    symbol_location: Option<u64>,
+    source_file: Option<String>,
+    source_line: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawImage {
+    name: Option<String>,
+    uuid: Option<String>,
+    arch: Option<String>,
+    base: Option<u64>,
+    size: Option<u64>,
+    path: Option<String>,
+}
+
+// ─── Parsing ─────────────────────────────────────────────────────────────────
+
+/// Parse the text of an `.ips` crash report.
+pub fn parse(text: &str) -> Result<CrashReport> {
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let (header_line, body_text) = text
+        .split_once('\n')
+        .context("not an .ips report: missing header line")?;
+
+    let header: RawHeader =
+        serde_json::from_str(header_line).context("invalid .ips header JSON")?;
+    let body: RawBody = serde_json::from_str(body_text).context("invalid .ips body JSON")?;
+
+    let images: Vec<BinaryImage> = body
+        .used_images
+        .into_iter()
+        .enumerate()
+        .map(|(i, img)| BinaryImage {
+            index: i as i64,
+            name: img.name.or_else(|| {
+                img.path
+                    .as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .map(str::to_string)
+            }),
+            uuid: img.uuid,
+            arch: img.arch,
+            base: img.base.map(|v| v as i64),
+            size: img.size.map(|v| v as i64),
+            path: img.path,
+        })
+        .collect();
+
+    let threads: Vec<Thread> = body
+        .threads
+        .into_iter()
+        .enumerate()
+        .map(|(ti, t)| Thread {
+            index: ti as i64,
+            name: t.name,
+            queue: t.queue,
+            triggered: t.triggered,
+            frames: t
+                .frames
+                .into_iter()
+                .enumerate()
+                .map(|(fi, f)| Frame {
+                    index: fi as i64,
+                    image_index: f.image_index,
+                    image_name: f
+                        .image_index
+                        .and_then(|i| images.get(i as usize))
+                        .and_then(|img| img.name.clone()),
+                    symbol: f.symbol,
+                    symbol_offset: f.symbol_location.map(|v| v as i64),
+                    image_offset: f.image_offset.map(|v| v as i64),
+                    source_file: f.source_file,
+                    source_line: f.source_line,
+                })
+                .collect(),
+        })
+        .collect();
+
+    let crashed_thread = body
+        .faulting_thread
+        .or_else(|| threads.iter().find(|t| t.triggered).map(|t| t.index));
+
+    let (exception_type, exception_subtype, signal) = match body.exception {
+        Some(e) => (e.kind, e.subtype, e.signal),
+        None => (None, None, None),
+    };
+
+    Ok(CrashReport {
+        app_version: header.app_version,
+        build_version: header.build_version,
+        exception_type,
+        exception_subtype,
+        signal,
+        termination_reason: body.termination.and_then(termination_reason),
+        crashed_thread,
+        threads,
+        images,
+    })
+}
+
+/// Render the termination block the way Apple's crash viewer does,
+/// e.g. `SIGNAL 11 Segmentation fault: 11`.
+fn termination_reason(t: RawTermination) -> Option<String> {
+    let prefix = match (t.namespace, t.code) {
+        (Some(ns), Some(code)) => Some(format!("{ns} {code}")),
+        (Some(ns), None) => Some(ns),
+        (None, Some(code)) => Some(code.to_string()),
+        (None, None) => None,
+    };
+    match (prefix, t.indicator) {
+        (Some(p), Some(i)) => Some(format!("{p} {i}")),
+        (Some(p), None) => Some(p),
+        (None, i) => i,
+    }
+}
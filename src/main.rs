@@ -2,11 +2,22 @@ mod auth;
 mod client;
 mod config;
 mod db;
+mod error;
+mod ips;
+mod query;
+mod signature;
+mod symbolicate;
 mod types;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand, ValueEnum};
-use db::{CrashDb, CrashFilters, CrashRow, FeedbackFilters, FeedbackRow, NewCrash, NewFeedback};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use db::{
+    CrashDb, CrashFilters, CrashRow, FeedbackFilters, FeedbackRow, GroupFilters, ListQuery,
+    NewCrash, NewFeedback,
+};
+use futures::stream::{self, StreamExt};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
@@ -19,9 +30,10 @@ use tracing::{info, warn};
     about = "Manage TestFlight crash feedback"
 )]
 struct Cli {
-    /// Output format.
+    /// Output format. csv, ndjson and markdown apply to list, feedback list,
+    /// view, stats, feedback stats and groups list.
     #[arg(long, default_value = "text", global = true)]
-    format: Format,
+    format: OutputFormat,
 
     /// Override data directory (default: ./asc-crashes or ~/.asc-crashes).
     #[arg(long, global = true)]
@@ -31,12 +43,51 @@ struct Cli {
     cmd: Cmd,
 }
 
-#[derive(Clone, ValueEnum)]
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+    /// One JSON object per line.
+    Ndjson,
+    /// A GitHub-flavored Markdown table.
+    Markdown,
+}
+
+impl OutputFormat {
+    /// The tabular format, if this is one.
+    fn table(self) -> Option<TableFormat> {
+        match self {
+            OutputFormat::Text | OutputFormat::Json => None,
+            OutputFormat::Csv => Some(TableFormat::Csv),
+            OutputFormat::Ndjson => Some(TableFormat::Ndjson),
+            OutputFormat::Markdown => Some(TableFormat::Markdown),
+        }
+    }
+
+    /// What commands without tabular output print: JSON for machine formats.
+    fn base(self) -> Format {
+        match self {
+            OutputFormat::Text | OutputFormat::Markdown => Format::Text,
+            OutputFormat::Json | OutputFormat::Csv | OutputFormat::Ndjson => Format::Json,
+        }
+    }
+}
+
+#[derive(Clone)]
 enum Format {
     Text,
     Json,
 }
 
+#[derive(Clone, Copy)]
+enum TableFormat {
+    Csv,
+    Ndjson,
+    Markdown,
+}
+
 #[derive(Subcommand)]
 enum Cmd {
     /// Create a new data directory with template config and database.
@@ -50,7 +101,10 @@ enum Cmd {
     Apps,
 
     /// Pull new crashes and feedback from Apple.
+    #[command(args_conflicts_with_subcommands = true)]
     Sync {
+        #[command(subcommand)]
+        cmd: Option<SyncCmd>,
         /// Sync only this app (bundle ID). Default: all configured apps.
         #[arg(long)]
         app: Option<String>,
@@ -60,11 +114,21 @@ enum Cmd {
         /// Skip crash sync (feedback only).
         #[arg(long)]
         no_crashes: bool,
+        /// Symbolicate newly downloaded logs against the configured dSYM directory.
+        #[arg(long)]
+        symbolicate: bool,
+        /// Parallel log/screenshot downloads. Default: [sync] concurrency.
+        #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        jobs: Option<usize>,
+        /// Walk every page instead of stopping at the last sync's high-water
+        /// mark, picking up submissions Apple back-filled.
+        #[arg(long)]
+        full: bool,
     },
 
     /// List crashes.
     List {
-        /// Filter by status (comma-separated: new,investigating,fixed,wontfix,duplicate).
+        /// Filter by status (comma-separated, e.g. new,investigating).
         #[arg(long)]
         status: Option<String>,
         /// Show only crashes since this date (ISO 8601).
@@ -73,17 +137,95 @@ enum Cmd {
         /// Filter by app bundle ID.
         #[arg(long)]
         app: Option<String>,
+        /// Filter by exception type from the parsed log (e.g. EXC_BAD_ACCESS).
+        #[arg(long)]
+        exception: Option<String>,
+        /// Show only crashes in this group.
+        #[arg(long)]
+        group: Option<i64>,
+        /// Show only crashes flagged as regressions of a fixed group.
+        #[arg(long)]
+        regressions: bool,
+        /// Also show crashes deleted or expired on App Store Connect.
+        #[arg(long, conflicts_with = "only_expired")]
+        include_expired: bool,
+        /// Show only crashes deleted or expired on App Store Connect.
+        #[arg(long)]
+        only_expired: bool,
+        /// Show only crashes assigned to this person.
+        #[arg(long, conflicts_with = "unassigned")]
+        assignee: Option<String>,
+        /// Show only crashes nobody is assigned to.
+        #[arg(long)]
+        unassigned: bool,
+        /// Show only crashes with any of these tags (repeatable or comma-separated).
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// With --tag, require every listed tag instead of any.
+        #[arg(long, requires = "tags")]
+        all_tags: bool,
+        #[command(flatten)]
+        query: QueryArgs,
         /// Max results.
         #[arg(long, default_value = "50")]
         limit: usize,
     },
 
+    /// Run a saved `[[views]]` query from config.toml. Lists the views when
+    /// no name is given.
+    View {
+        name: Option<String>,
+        /// Max results. Default: the view's `limit`, else 50.
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Skip this many results, for paging with --limit.
+        #[arg(long, default_value = "0")]
+        offset: usize,
+    },
+
     /// Show full details of a crash.
     Show { id: i64 },
 
+    /// Re-parse downloaded .ips logs into structured crash data.
+    Reparse {
+        /// Only re-parse this crash. Default: every crash with a log.
+        id: Option<i64>,
+    },
+
+    /// Resolve unsymbolicated frames using local dSYM bundles.
+    Symbolicate {
+        /// Only symbolicate this crash. Default: every crash with a log.
+        id: Option<i64>,
+        /// Directory to search for .dSYM bundles. Default: [symbolication] dsym_dir.
+        #[arg(long)]
+        dsym_dir: Option<PathBuf>,
+    },
+
     /// Print the absolute path to a crash log file.
     Log { id: i64 },
 
+    /// Full-text search over tester comments, fix notes, notes and crash
+    /// logs, across crashes and feedback.
+    Search {
+        /// Words to find; all must match. A trailing `*` matches a prefix.
+        #[arg(allow_hyphen_values = true)]
+        query: String,
+        /// Only this app (bundle ID).
+        #[arg(long)]
+        app: Option<String>,
+        /// Search crashes only.
+        #[arg(long, conflicts_with = "no_crashes")]
+        no_feedback: bool,
+        /// Search feedback only.
+        #[arg(long)]
+        no_crashes: bool,
+        /// Use FTS5 query syntax (OR, NOT, NEAR, "phrases", `log:term`).
+        #[arg(long)]
+        raw: bool,
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+
     /// Mark a crash as fixed.
     Fix {
         id: i64,
@@ -112,24 +254,200 @@ enum Cmd {
     /// Reset a crash status to "new".
     Reopen { id: i64 },
 
+    /// Move a crash to any status defined in [workflow].
+    Status {
+        id: i64,
+        /// The new status, e.g. needs-info.
+        status: String,
+        #[arg(long)]
+        notes: Option<String>,
+    },
+
+    /// Assign a crash to someone, or clear its assignee.
+    Assign {
+        id: i64,
+        /// Who to assign it to.
+        #[arg(long, required_unless_present = "none", conflicts_with = "none")]
+        to: Option<String>,
+        /// Clear the assignee.
+        #[arg(long)]
+        none: bool,
+    },
+
+    /// Add tags to a crash.
+    Tag {
+        id: i64,
+        /// Tags to add, e.g. networking p0.
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Remove tags from a crash.
+    Untag {
+        id: i64,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Add a timestamped note to a crash.
+    Note {
+        id: i64,
+        /// The note text.
+        text: String,
+    },
+
     /// Show crash statistics.
     Stats {
         #[arg(long)]
         app: Option<String>,
     },
 
+    /// Crash groups (crashes sharing a signature).
+    Groups {
+        #[command(subcommand)]
+        cmd: GroupsCmd,
+    },
+
+    /// TestFlight builds.
+    Builds {
+        #[command(subcommand)]
+        cmd: BuildsCmd,
+    },
+
     /// Manage screenshot feedback submissions.
     Feedback {
         #[command(subcommand)]
         cmd: FeedbackCmd,
     },
+
+    /// Inspect and upgrade the database schema.
+    Db {
+        #[command(subcommand)]
+        cmd: DbCmd,
+    },
+}
+
+/// Options `list` and `feedback list` share.
+#[derive(Args)]
+struct QueryArgs {
+    /// Filter expression over JSON field names, e.g.
+    /// 'device_model ~ "iPad%" and os_version >= "17" and battery_pct < 20'.
+    #[arg(long = "where", value_name = "EXPR")]
+    filter: Option<String>,
+    /// Show only items up to this date (ISO 8601, inclusive).
+    #[arg(long)]
+    until: Option<String>,
+    /// Show only this build: build number, app version or ASC build ID.
+    #[arg(long)]
+    build: Option<String>,
+    /// Sort by fields, e.g. battery_pct or os_version:desc,created_at.
+    /// Default: newest first.
+    #[arg(long, value_name = "FIELD[:desc],…")]
+    sort: Option<String>,
+    /// Skip this many results, for paging with --limit.
+    #[arg(long, default_value = "0")]
+    offset: usize,
+    /// Show only these columns, by JSON field name or `build`, e.g.
+    /// id,status,device_model,build,signature,tester_email.
+    #[arg(long, value_delimiter = ',', value_name = "FIELD,…")]
+    columns: Vec<String>,
+}
+
+impl QueryArgs {
+    fn into_list_query(self) -> Result<ListQuery> {
+        Ok(ListQuery {
+            filter: self
+                .filter
+                .as_deref()
+                .map(query::parse)
+                .transpose()
+                .context("invalid --where filter")?,
+            until: self.until,
+            build: self.build,
+            sort: self
+                .sort
+                .as_deref()
+                .map(query::parse_sort)
+                .transpose()
+                .context("invalid --sort")?
+                .unwrap_or_default(),
+            offset: self.offset,
+        })
+    }
+}
+
+#[derive(Subcommand)]
+enum DbCmd {
+    /// Show the schema version and any pending migrations.
+    Version,
+    /// Apply pending migrations, backing the database up first. Other
+    /// commands do this automatically.
+    Migrate {
+        /// List the migrations that would run without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SyncCmd {
+    /// Past sync runs, most recent first.
+    History {
+        /// Max results.
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum GroupsCmd {
+    /// List crash groups, most frequent first.
+    List {
+        /// Filter by app bundle ID.
+        #[arg(long)]
+        app: Option<String>,
+        /// Max results.
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Show a group with its affected builds, devices and crashes.
+    Show { id: i64 },
+    /// Mark a group fixed in a build. Its open crashes are marked fixed, and
+    /// future crashes with this signature are triaged against the build.
+    Fix {
+        id: i64,
+        /// The build (ASC build ID) that contains the fix.
+        #[arg(long)]
+        build: String,
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Reset a group to open.
+    Reopen { id: i64 },
+    /// Recompute signatures and regroup every parsed crash.
+    Rebuild,
+}
+
+#[derive(Subcommand)]
+enum BuildsCmd {
+    /// Per-build crash/feedback counts and signature changes, each build
+    /// compared against its predecessor.
+    Report {
+        /// App bundle ID. Optional when only one app is configured.
+        #[arg(long)]
+        app: Option<String>,
+        /// Show only the most recent N builds.
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // parsed once, never stored
 enum FeedbackCmd {
     /// List screenshot feedback.
     List {
-        /// Filter by status (comma-separated: new,investigating,fixed,wontfix,duplicate).
+        /// Filter by status (comma-separated, e.g. new,investigating).
         #[arg(long)]
         status: Option<String>,
         /// Show only feedback since this date (ISO 8601).
@@ -138,6 +456,26 @@ enum FeedbackCmd {
         /// Filter by app bundle ID.
         #[arg(long)]
         app: Option<String>,
+        /// Also show feedback deleted or expired on App Store Connect.
+        #[arg(long, conflicts_with = "only_expired")]
+        include_expired: bool,
+        /// Show only feedback deleted or expired on App Store Connect.
+        #[arg(long)]
+        only_expired: bool,
+        /// Show only feedback assigned to this person.
+        #[arg(long, conflicts_with = "unassigned")]
+        assignee: Option<String>,
+        /// Show only feedback nobody is assigned to.
+        #[arg(long)]
+        unassigned: bool,
+        /// Show only feedback with any of these tags (repeatable or comma-separated).
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// With --tag, require every listed tag instead of any.
+        #[arg(long, requires = "tags")]
+        all_tags: bool,
+        #[command(flatten)]
+        query: QueryArgs,
         /// Max results.
         #[arg(long, default_value = "50")]
         limit: usize,
@@ -169,6 +507,42 @@ enum FeedbackCmd {
     },
     /// Reset status to "new".
     Reopen { id: i64 },
+    /// Move feedback to any status defined in [workflow].
+    Status {
+        id: i64,
+        /// The new status, e.g. needs-info.
+        status: String,
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Assign feedback to someone, or clear its assignee.
+    Assign {
+        id: i64,
+        /// Who to assign it to.
+        #[arg(long, required_unless_present = "none", conflicts_with = "none")]
+        to: Option<String>,
+        /// Clear the assignee.
+        #[arg(long)]
+        none: bool,
+    },
+    /// Add tags to feedback.
+    Tag {
+        id: i64,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from feedback.
+    Untag {
+        id: i64,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Add a timestamped note to feedback.
+    Note {
+        id: i64,
+        /// The note text.
+        text: String,
+    },
     /// Show feedback statistics.
     Stats {
         #[arg(long)]
@@ -179,7 +553,7 @@ enum FeedbackCmd {
 // ─── Entry ───────────────────────────────────────────────────────────────────
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -189,13 +563,54 @@ async fn main() -> Result<()> {
         .init();
 
     let cli = Cli::parse();
+    let fmt = cli.format.base();
+    if let Err(e) = run(cli).await {
+        match fmt {
+            Format::Json => {
+                let report = error::report(&e);
+                eprintln!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).unwrap_or_else(|_| format!("{e:#}"))
+                );
+            }
+            Format::Text => eprintln!("Error: {e:?}"),
+        }
+        std::process::exit(error::exit_code(&e));
+    }
+}
 
+async fn run(cli: Cli) -> Result<()> {
     // `init` doesn't need an existing data dir
     if let Cmd::Init { global } = &cli.cmd {
         return cmd_init(*global);
     }
 
+    let out = cli.format;
+    let tabular = matches!(
+        cli.cmd,
+        Cmd::List { .. }
+            | Cmd::View { .. }
+            | Cmd::Stats { .. }
+            | Cmd::Groups {
+                cmd: GroupsCmd::List { .. }
+            }
+            | Cmd::Feedback {
+                cmd: FeedbackCmd::List { .. } | FeedbackCmd::Stats { .. }
+            }
+    );
+    if out.table().is_some() && !tabular {
+        anyhow::bail!(
+            "--format csv, ndjson and markdown work with list, feedback list, view, stats, \
+             feedback stats and groups list; use text or json here"
+        );
+    }
+    let fmt = &out.base();
+
     let data_dir = config::resolve_data_dir(cli.data_dir.as_deref())?;
+    // Schema commands work without (or with a broken) config.
+    if let Cmd::Db { cmd } = &cli.cmd {
+        return cmd_db(&data_dir.join("crashes.db"), cmd, fmt);
+    }
     if !data_dir.join("config.toml").exists() {
         anyhow::bail!(
             "No config found. Run `asc-crash-fetcher init` first.\n\
@@ -209,16 +624,41 @@ async fn main() -> Result<()> {
     let db = CrashDb::open(&db_path)?;
     let logs_dir = data_dir.join("logs");
     let screenshots_dir = data_dir.join("screenshots");
+    let actor = cfg.actor();
+    let wf = &cfg.workflow;
+    db.set_open_statuses(&wf.open)?;
 
     match cli.cmd {
-        Cmd::Init { .. } => unreachable!(),
-        Cmd::Apps => cmd_apps(&cfg, &cli.format).await,
+        Cmd::Init { .. } | Cmd::Db { .. } => unreachable!(),
+        Cmd::Apps => cmd_apps(&cfg, fmt).await,
         Cmd::Sync {
+            cmd: Some(SyncCmd::History { limit }),
+            ..
+        } => cmd_sync_history(&db, limit, fmt),
+        Cmd::Sync {
+            cmd: None,
             app,
             no_feedback,
             no_crashes,
+            symbolicate,
+            jobs,
+            full,
         } => {
-            cmd_sync(
+            // Fail before touching the network if there's nothing to load.
+            let mut symbolicator = if symbolicate {
+                Some(open_symbolicator(&cfg, None)?)
+            } else {
+                None
+            };
+            let apps: Vec<&str> = cfg
+                .apps
+                .iter()
+                .map(|a| a.bundle_id.as_str())
+                .filter(|b| app.as_deref().is_none_or(|f| f == *b))
+                .collect();
+            let run_id = db.start_sync_run(&apps, full)?;
+            let mut counts = db::SyncRunCounts::default();
+            let result = cmd_sync(
                 &cfg,
                 &db,
                 &logs_dir,
@@ -226,47 +666,189 @@ async fn main() -> Result<()> {
                 app.as_deref(),
                 no_feedback,
                 no_crashes,
-                &cli.format,
+                full,
+                symbolicator.as_mut(),
+                jobs.unwrap_or(cfg.sync.concurrency),
+                &mut counts,
+                fmt,
             )
-            .await
+            .await;
+            let exit_code = match &result {
+                Ok(()) => 0,
+                Err(e) => {
+                    counts.errors.push(format!("{e:#}"));
+                    error::exit_code(e)
+                }
+            };
+            // A failed sync's own error matters more than a failure to
+            // record it.
+            if let Err(e) = db.finish_sync_run(run_id, &counts, exit_code) {
+                match result {
+                    Ok(()) => return Err(e.context("failed to record sync run")),
+                    Err(_) => warn!(err = %format!("{e:#}"), "failed to record sync run"),
+                }
+            }
+            result
         }
         Cmd::List {
             status,
             since,
             app,
+            exception,
+            group,
+            regressions,
+            include_expired,
+            only_expired,
+            assignee,
+            unassigned,
+            tags,
+            all_tags,
+            query,
+            limit,
+        } => {
+            let columns = query.columns.clone();
+            let filters = CrashFilters {
+                status: parse_statuses(wf, status.as_deref())?,
+                since,
+                app_bundle_id: app,
+                exception_type: exception,
+                group_id: group,
+                regressions_only: regressions,
+                include_expired,
+                only_expired,
+                assignee,
+                unassigned,
+                tags: parse_tags(&tags)?,
+                all_tags,
+                query: query.into_list_query()?,
+                limit,
+            };
+            cmd_list(&db, wf, &filters, &columns, out)
+        }
+        Cmd::View {
+            name,
             limit,
-        } => cmd_list(&db, status, since, app, limit, &cli.format),
-        Cmd::Show { id } => cmd_show(&db, id, &cli.format),
+            offset,
+        } => cmd_view(&db, wf, &cfg.views, name.as_deref(), limit, offset, out),
+        Cmd::Show { id } => cmd_show(&db, id, fmt),
+        Cmd::Reparse { id } => cmd_reparse(&db, id, fmt),
+        Cmd::Symbolicate { id, dsym_dir } => {
+            let mut symbolicator = open_symbolicator(&cfg, dsym_dir.as_deref())?;
+            cmd_symbolicate(&db, &mut symbolicator, id, fmt)
+        }
         Cmd::Log { id } => cmd_log(&db, id),
-        Cmd::Fix { id, notes } => cmd_status(&db, id, "fixed", notes.as_deref(), &cli.format),
-        Cmd::Investigate { id } => cmd_status(&db, id, "investigating", None, &cli.format),
-        Cmd::Wontfix { id, notes } => cmd_status(&db, id, "wontfix", notes.as_deref(), &cli.format),
-        Cmd::Duplicate { id, of_id } => cmd_duplicate(&db, id, of_id, &cli.format),
-        Cmd::Reopen { id } => cmd_reopen(&db, id, &cli.format),
-        Cmd::Stats { app } => cmd_stats(&db, app.as_deref(), &cli.format),
+        Cmd::Search {
+            query,
+            app,
+            no_feedback,
+            no_crashes,
+            raw,
+            limit,
+        } => {
+            let filters = db::SearchFilters {
+                entity: if no_feedback {
+                    Some("crash")
+                } else if no_crashes {
+                    Some("feedback")
+                } else {
+                    None
+                },
+                app,
+                raw,
+                limit,
+            };
+            cmd_search(&db, &query, &filters, fmt)
+        }
+        Cmd::Fix { id, notes } => cmd_status(&db, wf, id, "fixed", notes.as_deref(), &actor, fmt),
+        Cmd::Investigate { id } => cmd_status(&db, wf, id, "investigating", None, &actor, fmt),
+        Cmd::Wontfix { id, notes } => {
+            cmd_status(&db, wf, id, "wontfix", notes.as_deref(), &actor, fmt)
+        }
+        Cmd::Duplicate { id, of_id } => cmd_duplicate(&db, wf, id, of_id, &actor, fmt),
+        Cmd::Reopen { id } => cmd_reopen(&db, wf, id, &actor, fmt),
+        Cmd::Status { id, status, notes } => {
+            cmd_status(&db, wf, id, &status, notes.as_deref(), &actor, fmt)
+        }
+        Cmd::Assign { id, to, .. } => cmd_assign(&db, "crash", id, to.as_deref(), fmt),
+        Cmd::Tag { id, tags } => cmd_tag(&db, "crash", id, &tags, true, fmt),
+        Cmd::Untag { id, tags } => cmd_tag(&db, "crash", id, &tags, false, fmt),
+        Cmd::Note { id, text } => cmd_note(&db, "crash", id, &text, &actor, fmt),
+        Cmd::Stats { app } => cmd_stats(&db, wf, app.as_deref(), out),
+        Cmd::Groups { cmd } => match cmd {
+            GroupsCmd::List { app, limit } => {
+                let filters = GroupFilters {
+                    app_bundle_id: app,
+                    limit,
+                };
+                cmd_groups_list(&db, &filters, out)
+            }
+            GroupsCmd::Show { id } => cmd_groups_show(&db, id, fmt),
+            GroupsCmd::Fix { id, build, notes } => {
+                cmd_groups_fix(&cfg, &db, id, &build, notes.as_deref(), &actor, fmt).await
+            }
+            GroupsCmd::Reopen { id } => cmd_groups_reopen(&db, id, fmt),
+            GroupsCmd::Rebuild => cmd_groups_rebuild(&db, fmt),
+        },
+        Cmd::Builds { cmd } => match cmd {
+            BuildsCmd::Report { app, limit } => {
+                cmd_builds_report(&cfg, &db, app.as_deref(), limit, fmt)
+            }
+        },
         Cmd::Feedback { cmd } => match cmd {
             FeedbackCmd::List {
                 status,
                 since,
                 app,
+                include_expired,
+                only_expired,
+                assignee,
+                unassigned,
+                tags,
+                all_tags,
+                query,
                 limit,
-            } => cmd_feedback_list(&db, status, since, app, limit, &cli.format),
-            FeedbackCmd::Show { id } => cmd_feedback_show(&db, id, &cli.format),
+            } => {
+                let columns = query.columns.clone();
+                let filters = FeedbackFilters {
+                    status: parse_statuses(wf, status.as_deref())?,
+                    since,
+                    app_bundle_id: app,
+                    include_expired,
+                    only_expired,
+                    assignee,
+                    unassigned,
+                    tags: parse_tags(&tags)?,
+                    all_tags,
+                    query: query.into_list_query()?,
+                    limit,
+                };
+                cmd_feedback_list(&db, wf, &filters, &columns, out)
+            }
+            FeedbackCmd::Show { id } => cmd_feedback_show(&db, id, fmt),
             FeedbackCmd::Screenshot { id } => cmd_feedback_screenshot(&db, id),
             FeedbackCmd::Fix { id, notes } => {
-                cmd_feedback_status(&db, id, "fixed", notes.as_deref(), &cli.format)
+                cmd_feedback_status(&db, wf, id, "fixed", notes.as_deref(), &actor, fmt)
             }
             FeedbackCmd::Investigate { id } => {
-                cmd_feedback_status(&db, id, "investigating", None, &cli.format)
+                cmd_feedback_status(&db, wf, id, "investigating", None, &actor, fmt)
             }
             FeedbackCmd::Wontfix { id, notes } => {
-                cmd_feedback_status(&db, id, "wontfix", notes.as_deref(), &cli.format)
+                cmd_feedback_status(&db, wf, id, "wontfix", notes.as_deref(), &actor, fmt)
             }
             FeedbackCmd::Duplicate { id, of_id } => {
-                cmd_feedback_duplicate(&db, id, of_id, &cli.format)
+                cmd_feedback_duplicate(&db, wf, id, of_id, &actor, fmt)
+            }
+            FeedbackCmd::Reopen { id } => cmd_feedback_reopen(&db, wf, id, &actor, fmt),
+            FeedbackCmd::Status { id, status, notes } => {
+                cmd_feedback_status(&db, wf, id, &status, notes.as_deref(), &actor, fmt)
             }
-            FeedbackCmd::Reopen { id } => cmd_feedback_reopen(&db, id, &cli.format),
-            FeedbackCmd::Stats { app } => cmd_feedback_stats(&db, app.as_deref(), &cli.format),
+            FeedbackCmd::Assign { id, to, .. } => {
+                cmd_assign(&db, "feedback", id, to.as_deref(), fmt)
+            }
+            FeedbackCmd::Tag { id, tags } => cmd_tag(&db, "feedback", id, &tags, true, fmt),
+            FeedbackCmd::Untag { id, tags } => cmd_tag(&db, "feedback", id, &tags, false, fmt),
+            FeedbackCmd::Note { id, text } => cmd_note(&db, "feedback", id, &text, &actor, fmt),
+            FeedbackCmd::Stats { app } => cmd_feedback_stats(&db, wf, app.as_deref(), out),
         },
     }
 }
@@ -299,11 +881,82 @@ fn cmd_init(global: bool) -> Result<()> {
     Ok(())
 }
 
+// ─── db ──────────────────────────────────────────────────────────────────────
+
+fn cmd_db(db_path: &Path, cmd: &DbCmd, fmt: &Format) -> Result<()> {
+    if !db_path.exists() {
+        anyhow::bail!(
+            "No database at {}. Run `asc-crash-fetcher init` first.",
+            db_path.display()
+        );
+    }
+    let db = CrashDb::open_unmigrated(db_path)?;
+
+    match cmd {
+        DbCmd::Version => {
+            let version = db.schema_version()?;
+            let pending = db.pending_migrations()?;
+            match fmt {
+                Format::Json => {
+                    let out = serde_json::json!({
+                        "version": version,
+                        "latest": db::SCHEMA_VERSION,
+                        "pending": pending,
+                    });
+                    println!("{}", serde_json::to_string_pretty(&out)?);
+                }
+                Format::Text => {
+                    println!("Schema version: {version} (latest {})", db::SCHEMA_VERSION);
+                    print_migrations("Pending", &pending);
+                }
+            }
+        }
+        DbCmd::Migrate { dry_run: true } => {
+            let pending = db.pending_migrations()?;
+            match fmt {
+                Format::Json => {
+                    let out = serde_json::json!({
+                        "from": db.schema_version()?,
+                        "to": pending.last().map_or(db.schema_version()?, |m| m.version),
+                        "pending": pending,
+                    });
+                    println!("{}", serde_json::to_string_pretty(&out)?);
+                }
+                Format::Text => print_migrations("Would apply", &pending),
+            }
+        }
+        DbCmd::Migrate { dry_run: false } => {
+            let report = db.migrate()?;
+            match fmt {
+                Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                Format::Text => {
+                    print_migrations("Applied", &report.applied);
+                    if let Some(ref backup) = report.backup {
+                        println!("Backup: {backup}");
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_migrations(label: &str, steps: &[db::MigrationStep]) {
+    if steps.is_empty() {
+        println!("Schema is up to date.");
+        return;
+    }
+    println!("{label}:");
+    for m in steps {
+        println!("  v{:<4} {}", m.version, m.description);
+    }
+}
+
 // ─── apps ────────────────────────────────────────────────────────────────────
 
 async fn cmd_apps(cfg: &config::Config, fmt: &Format) -> Result<()> {
     let client = make_client(cfg)?;
-    let apps = client.list_apps().await?;
+    let apps = client.list_apps().await.context("could not list apps")?;
 
     match fmt {
         Format::Json => {
@@ -351,6 +1004,10 @@ async fn cmd_sync(
     filter_app: Option<&str>,
     no_feedback: bool,
     no_crashes: bool,
+    full: bool,
+    mut symbolicator: Option<&mut symbolicate::Symbolicator>,
+    jobs: usize,
+    counts: &mut db::SyncRunCounts,
     fmt: &Format,
 ) -> Result<()> {
     std::fs::create_dir_all(logs_dir)?;
@@ -369,15 +1026,20 @@ async fn cmd_sync(
 
     let mut all_new_crashes: Vec<serde_json::Value> = Vec::new();
     let mut all_recovered_logs: Vec<serde_json::Value> = Vec::new();
+    let mut all_regressions: Vec<serde_json::Value> = Vec::new();
+    let mut all_auto_duplicates: Vec<serde_json::Value> = Vec::new();
+    let mut all_untriaged: Vec<serde_json::Value> = Vec::new();
     let mut all_new_feedbacks: Vec<serde_json::Value> = Vec::new();
     let mut all_recovered_screenshots: Vec<serde_json::Value> = Vec::new();
+    let mut all_sweeps: Vec<serde_json::Value> = Vec::new();
 
     for app_cfg in &apps_to_sync {
         let asc_app = client
             .find_app(&app_cfg.bundle_id)
-            .await?
-            .with_context(|| {
-                format!("app '{}' not found in App Store Connect", app_cfg.bundle_id)
+            .await
+            .with_context(|| format!("could not look up app '{}'", app_cfg.bundle_id))?
+            .ok_or_else(|| error::AscError::AppNotFound {
+                bundle_id: app_cfg.bundle_id.clone(),
             })?;
 
         let app_name = asc_app
@@ -395,14 +1057,15 @@ async fn cmd_sync(
         // ── Fetch new crash submissions ──────────────────────────────────
         let mut new_crashes: Vec<CrashRow> = Vec::new();
         if !no_crashes {
-            let mut url = client::AscClient::crash_list_url(&asc_app.id);
-            let mut page = 0u32;
+            let mut url = client.crash_list_url(&asc_app.id);
+            let state = db.get_sync_state(db_app_id, "crashes")?;
+            let mut sweep = Sweep::new(state.as_ref(), full);
 
-            'crash_pagination: loop {
-                page += 1;
-                info!(page, app = %app_cfg.bundle_id, "fetching crash page");
+            loop {
+                sweep.pages += 1;
+                counts.pages += 1;
+                info!(page = sweep.pages, app = %app_cfg.bundle_id, "fetching crash page");
                 let resp = client.get_crash_page(&url).await?;
-                let mut all_known_page = true;
 
                 for sub in &resp.data {
                     let attrs = sub.attributes.as_ref();
@@ -433,36 +1096,59 @@ async fn cmd_sync(
                         connection_type: attrs.and_then(|a| a.connection_type.clone()),
                     };
 
-                    if let Some(local_id) = db.insert_crash(&new_crash)? {
-                        all_known_page = false;
+                    let inserted = db.insert_crash(&new_crash)?;
+                    sweep.record(&sub.id, &new_crash.created_at, inserted.is_some());
+                    if let Some(local_id) = inserted {
+                        counts.new_crashes += 1;
                         if let Some(row) = db.get_crash(local_id)? {
                             new_crashes.push(row);
                         }
                     }
                 }
 
-                // If every entry on this page was already known, we've caught up
-                if all_known_page || resp.data.is_empty() {
-                    break 'crash_pagination;
+                if sweep.caught_up() {
+                    break;
                 }
-
                 match resp.links.next {
-                    Some(next) => url = next,
-                    None => break 'crash_pagination,
+                    Some(next) if next != url => url = next,
+                    Some(_) => {
+                        warn!("crash pagination returned the same page again, stopping");
+                        break;
+                    }
+                    None => {
+                        sweep.complete = true;
+                        break;
+                    }
                 }
+            }
+            sweep.save(db, db_app_id, "crashes")?;
+            sweep.reconcile(db, db_app_id, "crashes")?;
+            sweep.report(&app_cfg.bundle_id, "crashes", fmt, &mut all_sweeps);
 
-                if page >= 50 {
-                    warn!("hit 50 page limit, stopping crash pagination");
-                    break;
+            // ── Resolve build metadata ───────────────────────────────────
+            sync_builds(&client, db, db_app_id).await?;
+            for c in new_crashes.iter_mut() {
+                if let Some(fresh) = db.get_crash(c.id)? {
+                    *c = fresh;
                 }
             }
 
             // ── Download logs for new + retry missing ────────────────────
+            // Requests run `jobs` at a time; results are written to disk and
+            // the DB here, one at a time, as they complete.
             let mut recovered: Vec<CrashRow> = Vec::new();
             let missing = db.crashes_missing_logs()?;
+            let mut downloads = stream::iter(&missing)
+                .map(|crash| {
+                    let client = &client;
+                    async move { (crash, client.get_crash_log(&crash.submission_id).await) }
+                })
+                .buffer_unordered(jobs);
+            let mut progress = Progress::new("crash logs", missing.len(), fmt);
 
-            for crash in &missing {
-                match client.get_crash_log(&crash.submission_id).await {
+            while let Some((crash, result)) = downloads.next().await {
+                progress.tick();
+                match result {
                     Ok(Some(text)) => {
                         let path = logs_dir.join(format!("{}.ips", crash.id));
                         let abs = std::fs::canonicalize(logs_dir)
@@ -470,35 +1156,86 @@ async fn cmd_sync(
                             .join(format!("{}.ips", crash.id));
                         std::fs::write(&path, &text)?;
                         db.set_log(crash.id, &abs.to_string_lossy())?;
+                        counts.logs_downloaded += 1;
+                        let text = match symbolicator.as_deref_mut() {
+                            Some(sym) => match symbolicate_log(db, sym, crash, &abs, &text) {
+                                Ok(s) => s.text,
+                                Err(e) => {
+                                    warn!(id = crash.id, err = %e, "failed to symbolicate crash log");
+                                    text
+                                }
+                            },
+                            None => text,
+                        };
+                        parse_and_store(db, crash, &text)?;
 
-                        if let Some(c) = new_crashes.iter_mut().find(|c| c.id == crash.id) {
-                            c.has_log = true;
-                            c.log_path = Some(abs.to_string_lossy().to_string());
-                        } else {
-                            let mut updated = crash.clone();
-                            updated.has_log = true;
-                            updated.log_path = Some(abs.to_string_lossy().to_string());
-                            recovered.push(updated);
-                        }
+                        let target = match new_crashes.iter_mut().find(|c| c.id == crash.id) {
+                            Some(c) => c,
+                            None => {
+                                recovered.push(crash.clone());
+                                recovered.last_mut().unwrap()
+                            }
+                        };
+                        target.has_log = true;
+                        target.log_path = Some(abs.to_string_lossy().to_string());
                     }
                     Ok(None) => {} // not available yet
                     Err(e) => {
                         warn!(id = crash.id, err = %e, "failed to download crash log");
+                        counts
+                            .errors
+                            .push(format!("crash #{} log: {e:#}", crash.id));
+                    }
+                }
+            }
+            progress.finish();
+            recovered.sort_by_key(|c| c.id);
+
+            // ── Parse logs downloaded before the parser existed ──────────
+            for crash in db.crashes_missing_reports()? {
+                if let Some(p) = report_log_path(&crash) {
+                    match std::fs::read_to_string(p) {
+                        Ok(text) => {
+                            parse_and_store(db, &crash, &text)?;
+                        }
+                        Err(e) => warn!(id = crash.id, err = %e, "failed to read crash log"),
                     }
                 }
             }
+            for crash in db.crashes_missing_groups()? {
+                if let Some(report) = db.get_report(crash.id)? {
+                    assign_group(db, &crash, &report)?;
+                }
+            }
+
+            // ── Triage crashes that landed in a fixed group ──────────────
+            let mut triaged: Vec<(i64, GroupOutcome)> = Vec::new();
+            for c in new_crashes.iter_mut().chain(recovered.iter_mut()) {
+                let Some(fresh) = db.get_crash(c.id)? else {
+                    continue;
+                };
+                if let Some(outcome) = apply_group_status(db, &fresh)? {
+                    triaged.push((c.id, outcome));
+                }
+                *c = db.get_crash(c.id)?.unwrap_or(fresh);
+            }
 
             // ── Crash output ─────────────────────────────────────────────
             match fmt {
                 Format::Text => {
                     for c in &new_crashes {
                         eprintln!(
-                            "  [CRASH] #{:<4} {} / {}  {}",
+                            "  [CRASH] #{:<4} {} / {}  {}  {}",
                             c.id,
                             c.device_model.as_deref().unwrap_or("?"),
                             c.os_version.as_deref().unwrap_or("?"),
                             &c.created_at[..19.min(c.created_at.len())],
+                            db::build_label(c.app_version.as_deref(), c.build_number.as_deref())
+                                .unwrap_or_default(),
                         );
+                        if let Some(ref e) = c.exception_type {
+                            eprintln!("          {e}");
+                        }
                         if let Some(ref p) = c.log_path {
                             eprintln!("          → {p}");
                         } else {
@@ -512,6 +1249,32 @@ async fn cmd_sync(
                             c.log_path.as_deref().unwrap_or("?")
                         );
                     }
+                    for (id, outcome) in &triaged {
+                        match outcome {
+                            GroupOutcome::Regression {
+                                group_id,
+                                fixed_build,
+                            } => eprintln!(
+                                "  [REGRESSION] #{id} matches group #{group_id} \
+                                 (fixed in build {fixed_build})"
+                            ),
+                            GroupOutcome::Duplicate {
+                                group_id,
+                                of_id,
+                                fixed_build,
+                            } => eprintln!(
+                                "  [DUPLICATE]  #{id} → #{of_id}, older build than \
+                                 group #{group_id} fix ({fixed_build})"
+                            ),
+                            GroupOutcome::Untriaged {
+                                group_id,
+                                fixed_build,
+                            } => eprintln!(
+                                "  [UNTRIAGED]  #{id} matches fixed group #{group_id}, but its \
+                                 build can't be ordered against {fixed_build}"
+                            ),
+                        }
+                    }
                     if !new_crashes.is_empty() || !recovered.is_empty() {
                         let log_count =
                             new_crashes.iter().filter(|c| c.has_log).count() + recovered.len();
@@ -532,6 +1295,36 @@ async fn cmd_sync(
                             "log_path": c.log_path,
                         }));
                     }
+                    for (id, outcome) in &triaged {
+                        match outcome {
+                            GroupOutcome::Regression {
+                                group_id,
+                                fixed_build,
+                            } => all_regressions.push(serde_json::json!({
+                                "id": id,
+                                "group_id": group_id,
+                                "fixed_build_id": fixed_build,
+                            })),
+                            GroupOutcome::Duplicate {
+                                group_id,
+                                of_id,
+                                fixed_build,
+                            } => all_auto_duplicates.push(serde_json::json!({
+                                "id": id,
+                                "duplicate_of": of_id,
+                                "group_id": group_id,
+                                "fixed_build_id": fixed_build,
+                            })),
+                            GroupOutcome::Untriaged {
+                                group_id,
+                                fixed_build,
+                            } => all_untriaged.push(serde_json::json!({
+                                "id": id,
+                                "group_id": group_id,
+                                "fixed_build_id": fixed_build,
+                            })),
+                        }
+                    }
                 }
             }
         }
@@ -539,14 +1332,15 @@ async fn cmd_sync(
         // ── Fetch new feedback submissions ───────────────────────────────
         let mut new_feedbacks: Vec<FeedbackRow> = Vec::new();
         if !no_feedback {
-            let mut url = client::AscClient::screenshot_list_url(&asc_app.id);
-            let mut page = 0u32;
+            let mut url = client.screenshot_list_url(&asc_app.id);
+            let state = db.get_sync_state(db_app_id, "feedbacks")?;
+            let mut sweep = Sweep::new(state.as_ref(), full);
 
-            'feedback_pagination: loop {
-                page += 1;
-                info!(page, app = %app_cfg.bundle_id, "fetching feedback page");
+            loop {
+                sweep.pages += 1;
+                counts.pages += 1;
+                info!(page = sweep.pages, app = %app_cfg.bundle_id, "fetching feedback page");
                 let resp = client.get_screenshot_page(&url).await?;
-                let mut all_known_page = true;
 
                 for sub in &resp.data {
                     let attrs = sub.attributes.as_ref();
@@ -575,36 +1369,62 @@ async fn cmd_sync(
                         connection_type: attrs.and_then(|a| a.connection_type.clone()),
                     };
 
-                    if let Some(local_id) = db.insert_feedback(&new_feedback)? {
-                        all_known_page = false;
+                    let inserted = db.insert_feedback(&new_feedback)?;
+                    sweep.record(&sub.id, &new_feedback.created_at, inserted.is_some());
+                    if let Some(local_id) = inserted {
+                        counts.new_feedbacks += 1;
                         if let Some(row) = db.get_feedback(local_id)? {
                             new_feedbacks.push(row);
                         }
                     }
                 }
 
-                // If every entry on this page was already known, we've caught up
-                if all_known_page || resp.data.is_empty() {
-                    break 'feedback_pagination;
+                if sweep.caught_up() {
+                    break;
                 }
-
                 match resp.links.next {
-                    Some(next) => url = next,
-                    None => break 'feedback_pagination,
+                    Some(next) if next != url => url = next,
+                    Some(_) => {
+                        warn!("feedback pagination returned the same page again, stopping");
+                        break;
+                    }
+                    None => {
+                        sweep.complete = true;
+                        break;
+                    }
                 }
+            }
+            sweep.save(db, db_app_id, "feedbacks")?;
+            sweep.reconcile(db, db_app_id, "feedbacks")?;
+            sweep.report(&app_cfg.bundle_id, "feedbacks", fmt, &mut all_sweeps);
 
-                if page >= 50 {
-                    warn!("hit 50 page limit, stopping feedback pagination");
-                    break;
+            // ── Resolve build metadata ───────────────────────────────────
+            sync_builds(&client, db, db_app_id).await?;
+            for f in new_feedbacks.iter_mut() {
+                if let Some(fresh) = db.get_feedback(f.id)? {
+                    *f = fresh;
                 }
             }
 
             // ── Download screenshots for new + retry missing ─────────────
             let mut recovered_screenshots: Vec<FeedbackRow> = Vec::new();
             let missing = db.feedbacks_missing_screenshots()?;
+            let mut downloads = stream::iter(&missing)
+                .map(|feedback| {
+                    let client = &client;
+                    async move {
+                        (
+                            feedback,
+                            client.get_screenshot(&feedback.submission_id).await,
+                        )
+                    }
+                })
+                .buffer_unordered(jobs);
+            let mut progress = Progress::new("screenshots", missing.len(), fmt);
 
-            for feedback in &missing {
-                match client.get_screenshot(&feedback.submission_id).await {
+            while let Some((feedback, result)) = downloads.next().await {
+                progress.tick();
+                match result {
                     Ok(Some((bytes, mime_type))) => {
                         let ext = mime_to_ext(&mime_type);
                         let path = screenshots_dir.join(format!("{}.{}", feedback.id, ext));
@@ -613,6 +1433,7 @@ async fn cmd_sync(
                             .join(format!("{}.{}", feedback.id, ext));
                         std::fs::write(&path, &bytes)?;
                         db.set_screenshot(feedback.id, &abs.to_string_lossy(), &mime_type)?;
+                        counts.screenshots_downloaded += 1;
 
                         if let Some(f) = new_feedbacks.iter_mut().find(|f| f.id == feedback.id) {
                             f.has_screenshot = true;
@@ -629,20 +1450,27 @@ async fn cmd_sync(
                     Ok(None) => {} // not available yet
                     Err(e) => {
                         warn!(id = feedback.id, err = %e, "failed to download screenshot");
+                        counts
+                            .errors
+                            .push(format!("feedback #{} screenshot: {e:#}", feedback.id));
                     }
                 }
             }
+            progress.finish();
+            recovered_screenshots.sort_by_key(|f| f.id);
 
             // ── Feedback output ──────────────────────────────────────────
             match fmt {
                 Format::Text => {
                     for f in &new_feedbacks {
                         eprintln!(
-                            "  [FEEDBACK] #{:<4} {} / {}  {}",
+                            "  [FEEDBACK] #{:<4} {} / {}  {}  {}",
                             f.id,
                             f.device_model.as_deref().unwrap_or("?"),
                             f.os_version.as_deref().unwrap_or("?"),
                             &f.created_at[..19.min(f.created_at.len())],
+                            db::build_label(f.app_version.as_deref(), f.build_number.as_deref())
+                                .unwrap_or_default(),
                         );
                         if let Some(ref p) = f.screenshot_path {
                             eprintln!("             → {p}");
@@ -699,8 +1527,12 @@ async fn cmd_sync(
             let out = serde_json::json!({
                 "new_crashes": all_new_crashes,
                 "recovered_logs": all_recovered_logs,
+                "regressions": all_regressions,
+                "auto_duplicates": all_auto_duplicates,
+                "untriaged": all_untriaged,
                 "new_feedbacks": all_new_feedbacks,
                 "recovered_screenshots": all_recovered_screenshots,
+                "sweeps": all_sweeps,
                 "crash_total": crash_total,
                 "crash_unfixed": crash_unfixed,
                 "feedback_total": feedback_total,
@@ -713,64 +1545,398 @@ async fn cmd_sync(
     Ok(())
 }
 
-// ─── list ────────────────────────────────────────────────────────────────────
-
-fn cmd_list(
-    db: &CrashDb,
-    status: Option<String>,
-    since: Option<String>,
-    app: Option<String>,
-    limit: usize,
-    fmt: &Format,
-) -> Result<()> {
-    let filters = CrashFilters {
-        status: status.map(|s| s.split(',').map(|x| x.trim().to_string()).collect()),
-        since,
-        app_bundle_id: app,
-        limit,
-    };
-    let crashes = db.list_crashes(&filters)?;
+fn cmd_sync_history(db: &CrashDb, limit: usize, fmt: &Format) -> Result<()> {
+    let runs = db.list_sync_runs(limit)?;
 
     match fmt {
         Format::Json => {
             let out = serde_json::json!({
-                "crashes": crashes,
-                "count": crashes.len(),
+                "runs": runs,
+                "count": runs.len(),
             });
             println!("{}", serde_json::to_string_pretty(&out)?);
         }
         Format::Text => {
-            if crashes.is_empty() {
-                println!("No crashes found.");
+            if runs.is_empty() {
+                println!("No sync runs recorded.");
                 return Ok(());
             }
             println!(
-                " {:<5} {:<14} {:<20} {:<14} {:<10} APP",
-                "ID", "STATUS", "DATE", "DEVICE", "OS"
+                " {:<5} {:<20} {:>8} {:<10} {:>5} {:>7} {:>8} {:>5} {:>5} {:>6}  APPS",
+                "ID",
+                "STARTED",
+                "TOOK",
+                "STATUS",
+                "PAGES",
+                "CRASHES",
+                "FEEDBACK",
+                "LOGS",
+                "SHOTS",
+                "ERRORS"
             );
-            println!("{}", "-".repeat(90));
-            for c in &crashes {
-                let date = if c.created_at.len() >= 19 {
-                    &c.created_at[..19]
-                } else {
-                    &c.created_at
+            println!("{}", "-".repeat(112));
+            for r in &runs {
+                let took = r
+                    .finished_at
+                    .as_deref()
+                    .and_then(|f| sqlite_duration(&r.started_at, f))
+                    .map(|d| format!("{}s", d.num_seconds()))
+                    .unwrap_or_else(|| "-".into());
+                let status = match (r.status.as_str(), r.exit_code) {
+                    ("failed", Some(code)) => format!("failed({code})"),
+                    (s, _) => s.to_string(),
                 };
                 println!(
-                    " {:<5} {:<14} {:<20} {:<14} {:<10} {}",
-                    c.id,
-                    c.status,
-                    date,
-                    c.device_model.as_deref().unwrap_or("-"),
+                    " {:<5} {:<20} {:>8} {:<10} {:>5} {:>7} {:>8} {:>5} {:>5} {:>6}  {}{}",
+                    r.id,
+                    r.started_at,
+                    took,
+                    status,
+                    r.counts.pages,
+                    r.counts.new_crashes,
+                    r.counts.new_feedbacks,
+                    r.counts.logs_downloaded,
+                    r.counts.screenshots_downloaded,
+                    r.counts.errors.len(),
+                    r.apps.join(","),
+                    if r.full { " (full)" } else { "" },
+                );
+                for e in &r.counts.errors {
+                    println!("       ! {e}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Time between two `datetime('now')` timestamps.
+fn sqlite_duration(start: &str, end: &str) -> Option<chrono::Duration> {
+    let parse = |s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok();
+    Some(parse(end)? - parse(start)?)
+}
+
+/// One walk through a resource's pages, newest first. An incremental sweep
+/// stops once a page reaches back past the previous high-water mark; a full
+/// sweep (requested, or the first for this resource) reads every page.
+struct Sweep {
+    full: bool,
+    watermark: Option<String>,
+    pages: u32,
+    seen: usize,
+    new: usize,
+    /// New rows older than the previous high-water mark: back-filled by
+    /// Apple or left behind by an interrupted sync.
+    reconciled: usize,
+    newest: Option<String>,
+    oldest_on_page: Option<String>,
+    /// Reached the last page, so `seen_ids` is everything Apple still has.
+    complete: bool,
+    /// Read back past the previous high-water mark.
+    reached_watermark: bool,
+    seen_ids: HashSet<String>,
+    /// Local rows the API no longer returns, and ones that reappeared.
+    remote_deleted: usize,
+    restored: usize,
+}
+
+impl Sweep {
+    fn new(state: Option<&db::SyncState>, full: bool) -> Self {
+        let watermark = state.and_then(|s| s.last_created_at.clone());
+        Self {
+            full: full || watermark.is_none(),
+            watermark,
+            pages: 0,
+            seen: 0,
+            new: 0,
+            reconciled: 0,
+            newest: None,
+            oldest_on_page: None,
+            complete: false,
+            reached_watermark: false,
+            seen_ids: HashSet::new(),
+            remote_deleted: 0,
+            restored: 0,
+        }
+    }
+
+    fn record(&mut self, submission_id: &str, created_at: &str, inserted: bool) {
+        self.seen += 1;
+        if self.full {
+            self.seen_ids.insert(submission_id.to_string());
+        }
+        if inserted {
+            self.new += 1;
+            if self.watermark.as_deref().is_some_and(|w| created_at < w) {
+                self.reconciled += 1;
+            }
+        }
+        if created_at.is_empty() {
+            return;
+        }
+        if self.newest.as_deref().is_none_or(|n| created_at > n) {
+            self.newest = Some(created_at.to_string());
+        }
+        if self
+            .oldest_on_page
+            .as_deref()
+            .is_none_or(|o| created_at < o)
+        {
+            self.oldest_on_page = Some(created_at.to_string());
+        }
+    }
+
+    /// After a full sweep that reached the last page, flag rows Apple no
+    /// longer returns (deleted, or past the 120-day expiry).
+    fn reconcile(&mut self, db: &CrashDb, app_id: i64, resource: &str) -> Result<()> {
+        if self.full && self.complete {
+            (self.remote_deleted, self.restored) =
+                db.reconcile_remote(app_id, resource, &self.seen_ids)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the page just read reached back past the high-water mark.
+    /// Resets the per-page state for the next page.
+    fn caught_up(&mut self) -> bool {
+        let oldest = self.oldest_on_page.take();
+        if self.full {
+            return false;
+        }
+        self.reached_watermark = match (oldest, &self.watermark) {
+            (Some(o), Some(w)) => o < *w,
+            // An empty page: nothing further back.
+            (None, _) => true,
+            (Some(_), None) => false,
+        };
+        self.reached_watermark
+    }
+
+    /// Every page down to the previous high-water mark (or, for a full
+    /// sweep, the last page) was read.
+    fn covered(&self) -> bool {
+        self.complete || self.reached_watermark
+    }
+
+    /// Move the high-water mark, and stamp a full sweep, only if nothing
+    /// was skipped: a sweep cut short leaves them, so the next sync reads
+    /// the unread pages instead of stopping at the new mark.
+    fn save(&self, db: &CrashDb, app_id: i64, resource: &str) -> Result<()> {
+        if !self.covered() {
+            warn!(
+                resource,
+                "sync stopped before the last page; keeping the high-water mark"
+            );
+            return Ok(());
+        }
+        db.record_sweep(app_id, resource, self.newest.as_deref(), self.full)
+    }
+
+    fn report(
+        &self,
+        bundle_id: &str,
+        resource: &str,
+        fmt: &Format,
+        sweeps: &mut Vec<serde_json::Value>,
+    ) {
+        match fmt {
+            Format::Text => {
+                let mut line = format!(
+                    "  {resource}: {} page(s), {} seen, {} new",
+                    self.pages, self.seen, self.new
+                );
+                if self.reconciled > 0 {
+                    line.push_str(&format!(", {} reconciled", self.reconciled));
+                }
+                if self.remote_deleted > 0 {
+                    line.push_str(&format!(
+                        ", {} gone from App Store Connect",
+                        self.remote_deleted
+                    ));
+                }
+                if self.restored > 0 {
+                    line.push_str(&format!(", {} back on App Store Connect", self.restored));
+                }
+                if self.full {
+                    line.push_str(" (full sweep)");
+                }
+                if !self.covered() {
+                    line.push_str(" (stopped early)");
+                }
+                eprintln!("{line}");
+            }
+            Format::Json => sweeps.push(serde_json::json!({
+                "bundle_id": bundle_id,
+                "resource": resource,
+                "full": self.full,
+                "pages": self.pages,
+                "seen": self.seen,
+                "new": self.new,
+                "reconciled": self.reconciled,
+                "remote_deleted": self.remote_deleted,
+                "restored": self.restored,
+                "complete": self.covered(),
+                "last_created_at": if self.covered() {
+                    self.newest.as_deref().max(self.watermark.as_deref())
+                } else {
+                    self.watermark.as_deref()
+                },
+            })),
+        }
+    }
+}
+
+/// Fetch metadata for every build an app's crashes/feedbacks reference that
+/// isn't in the `builds` table yet. Builds Apple no longer knows about are
+/// stored without metadata so they aren't requested again.
+async fn sync_builds(client: &client::AscClient, db: &CrashDb, app_id: i64) -> Result<()> {
+    for build_id in db.builds_missing(app_id)? {
+        info!(build_id, "fetching build");
+        match client.get_build(&build_id).await {
+            Ok(Some(resp)) => db.upsert_build(&new_build(app_id, resp))?,
+            Ok(None) => {
+                warn!(build_id, "build not found");
+                db.upsert_build(&db::NewBuild {
+                    id: build_id,
+                    app_id,
+                    version: None,
+                    build_number: None,
+                    platform: None,
+                    uploaded_at: None,
+                    expires_at: None,
+                    expired: None,
+                    min_os_version: None,
+                })?;
+            }
+            Err(e) => warn!(build_id, err = %e, "failed to fetch build"),
+        }
+    }
+    Ok(())
+}
+
+fn new_build(app_id: i64, resp: types::BuildResponse) -> db::NewBuild {
+    let build = resp.data;
+    let version_id = build
+        .relationships
+        .as_ref()
+        .and_then(|r| r.pre_release_version.as_ref())
+        .and_then(|r| r.data.as_ref())
+        .map(|d| d.id.clone());
+    let pre_release = resp
+        .included
+        .into_iter()
+        .filter(|i| i.kind == "preReleaseVersions")
+        .find(|i| version_id.is_none() || version_id.as_deref() == Some(i.id.as_str()))
+        .and_then(|i| i.attributes);
+    let attrs = build.attributes;
+
+    db::NewBuild {
+        id: build.id,
+        app_id,
+        version: pre_release.as_ref().and_then(|p| p.version.clone()),
+        build_number: attrs.as_ref().and_then(|a| a.version.clone()),
+        platform: pre_release.and_then(|p| p.platform),
+        uploaded_at: attrs
+            .as_ref()
+            .and_then(|a| a.uploaded_date)
+            .map(|d| d.to_rfc3339()),
+        expires_at: attrs
+            .as_ref()
+            .and_then(|a| a.expiration_date)
+            .map(|d| d.to_rfc3339()),
+        expired: attrs.as_ref().and_then(|a| a.expired),
+        min_os_version: attrs.and_then(|a| a.min_os_version),
+    }
+}
+
+// ─── list ────────────────────────────────────────────────────────────────────
+
+/// Split a `--status a,b` filter, rejecting statuses not in `[workflow]`.
+fn parse_statuses(wf: &config::WorkflowConfig, arg: Option<&str>) -> Result<Option<Vec<String>>> {
+    let Some(arg) = arg else {
+        return Ok(None);
+    };
+    let statuses: Vec<String> = arg.split(',').map(|x| x.trim().to_string()).collect();
+    for s in &statuses {
+        wf.check_status(s)?;
+    }
+    Ok(Some(statuses))
+}
+
+/// Columns for `list --format markdown`; csv and ndjson get every field.
+const CRASH_SUMMARY_COLUMNS: &[&str] = &[
+    "id",
+    "status",
+    "created_at",
+    "device_model",
+    "os_version",
+    "build",
+    "exception_type",
+    "app_bundle_id",
+];
+
+fn cmd_list(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    filters: &CrashFilters,
+    columns: &[String],
+    out: OutputFormat,
+) -> Result<()> {
+    check_columns(db::CRASH_FIELDS, columns)?;
+    let crashes = db.list_crashes(filters)?;
+
+    if !columns.is_empty() {
+        return print_columns("crashes", columns, &crashes, out);
+    }
+    if let Some(t) = out.table() {
+        let all = field_names(db::CRASH_FIELDS);
+        return print_table(t, t.columns(&all, CRASH_SUMMARY_COLUMNS), &crashes);
+    }
+    match out.base() {
+        Format::Json => {
+            let out = serde_json::json!({
+                "crashes": crashes,
+                "count": crashes.len(),
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            if crashes.is_empty() {
+                println!("No crashes found.");
+                return Ok(());
+            }
+            println!(
+                " {:<5} {:<14} {:<20} {:<14} {:<10} {:<14} {:<20} APP",
+                "ID", "STATUS", "DATE", "DEVICE", "OS", "BUILD", "EXCEPTION"
+            );
+            println!("{}", "-".repeat(126));
+            for c in &crashes {
+                let date = if c.created_at.len() >= 19 {
+                    &c.created_at[..19]
+                } else {
+                    &c.created_at
+                };
+                println!(
+                    "{}{:<5} {:<14} {:<20} {:<14} {:<10} {:<14} {:<20} {}",
+                    expired_marker(c.remote_deleted_at.as_deref()),
+                    c.id,
+                    c.status,
+                    date,
+                    c.device_model.as_deref().unwrap_or("-"),
                     c.os_version.as_deref().unwrap_or("-"),
+                    db::build_label(c.app_version.as_deref(), c.build_number.as_deref())
+                        .as_deref()
+                        .unwrap_or("-"),
+                    c.exception_type.as_deref().unwrap_or("-"),
                     c.app_bundle_id.as_deref().unwrap_or("-"),
                 );
             }
             println!();
-            let unfixed = crashes
-                .iter()
-                .filter(|c| c.status == "new" || c.status == "investigating")
-                .count();
+            let unfixed = crashes.iter().filter(|c| wf.is_open(&c.status)).count();
             println!("{} crash(es) shown ({unfixed} unfixed)", crashes.len());
+            if crashes.iter().any(|c| c.remote_deleted_at.is_some()) {
+                println!("* no longer on App Store Connect (local copy only)");
+            }
         }
     }
     Ok(())
@@ -782,17 +1948,38 @@ fn cmd_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
     let crash = db
         .get_crash(id)?
         .with_context(|| format!("crash #{id} not found"))?;
+    let report = db.get_report(id)?;
+    let build = match crash.build_id {
+        Some(ref b) => db.get_build(b)?,
+        None => None,
+    };
+    let history = db.status_events("crash", id)?;
+    let comments = db.comments("crash", id)?;
 
     match fmt {
         Format::Json => {
-            println!("{}", serde_json::to_string_pretty(&crash)?);
+            let mut out = serde_json::to_value(&crash)?;
+            out["build"] = serde_json::to_value(&build)?;
+            out["report"] = serde_json::to_value(&report)?;
+            out["history"] = serde_json::to_value(&history)?;
+            out["comments"] = serde_json::to_value(&comments)?;
+            println!("{}", serde_json::to_string_pretty(&out)?);
         }
         Format::Text => {
             println!("Crash #{}", crash.id);
             println!("{}", "─".repeat(40));
             println!("Status:     {}", crash.status);
+            if let Some(ref v) = crash.assignee {
+                println!("Assignee:   {v}");
+            }
+            if !crash.tags.is_empty() {
+                println!("Tags:       {}", crash.tags.join(", "));
+            }
             println!("Created:    {}", crash.created_at);
             println!("Synced:     {}", crash.synced_at);
+            if let Some(ref at) = crash.remote_deleted_at {
+                println!("Remote:     gone from App Store Connect since {at} (local copy only)");
+            }
             if let Some(ref v) = crash.device_model {
                 println!("Device:     {v}");
             }
@@ -821,6 +2008,14 @@ fn cmd_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
             if let Some(ref v) = crash.app_name {
                 println!("App Name:   {v}");
             }
+            if let Some(v) =
+                db::build_label(crash.app_version.as_deref(), crash.build_number.as_deref())
+            {
+                println!("Build:      {v}");
+            }
+            if let Some(v) = build.as_ref().and_then(|b| b.uploaded_at.as_deref()) {
+                println!("Uploaded:   {v}");
+            }
             if let Some(v) = crash.app_uptime_ms {
                 println!("Uptime:     {:.1}s", v as f64 / 1000.0);
             }
@@ -839,9 +2034,27 @@ fn cmd_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
             if let Some(v) = crash.duplicate_of {
                 println!("Dup Of:     #{v}");
             }
+            if let Some(v) = crash.group_id {
+                println!(
+                    "Group:      #{v} ({})",
+                    crash.group_hash.as_deref().unwrap_or("?")
+                );
+            }
+            if let Some(v) = crash.regression_of {
+                println!("Regression: group #{v} was marked fixed");
+            }
+            print_history(&history);
+            print_comments(&comments);
+
+            if let Some(ref r) = report {
+                print_report_summary(r);
+            }
 
             if let Some(ref p) = crash.log_path {
                 println!("Log:        {p}");
+                if let Some(ref p) = crash.symbolicated_log_path {
+                    println!("Sym. Log:   {p}");
+                }
                 if let Ok(text) = std::fs::read_to_string(p) {
                     println!();
                     println!("--- Crash log (first 50 lines) ---");
@@ -861,6 +2074,138 @@ fn cmd_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
     Ok(())
 }
 
+// ─── reparse ─────────────────────────────────────────────────────────────────
+
+fn cmd_reparse(db: &CrashDb, id: Option<i64>, fmt: &Format) -> Result<()> {
+    let crashes = match id {
+        Some(id) => {
+            let crash = db
+                .get_crash(id)?
+                .with_context(|| format!("crash #{id} not found"))?;
+            if crash.log_path.is_none() {
+                anyhow::bail!("crash #{id}: no log available");
+            }
+            vec![crash]
+        }
+        None => db.crashes_with_logs()?,
+    };
+
+    let mut parsed = 0;
+    let mut failed: Vec<i64> = Vec::new();
+    for crash in &crashes {
+        let Some(p) = report_log_path(crash) else {
+            continue;
+        };
+        let text =
+            std::fs::read_to_string(p).with_context(|| format!("could not read crash log: {p}"))?;
+        match parse_and_store(db, crash, &text)? {
+            Some(_) => parsed += 1,
+            None => failed.push(crash.id),
+        }
+    }
+    db.prune_empty_groups()?;
+
+    match fmt {
+        Format::Json => {
+            let out = serde_json::json!({
+                "parsed": parsed,
+                "failed": failed,
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            eprintln!("{parsed} log(s) parsed, {} failed", failed.len());
+        }
+    }
+    Ok(())
+}
+
+// ─── symbolicate ─────────────────────────────────────────────────────────────
+
+fn cmd_symbolicate(
+    db: &CrashDb,
+    symbolicator: &mut symbolicate::Symbolicator,
+    id: Option<i64>,
+    fmt: &Format,
+) -> Result<()> {
+    let crashes = match id {
+        Some(id) => {
+            let crash = db
+                .get_crash(id)?
+                .with_context(|| format!("crash #{id} not found"))?;
+            if crash.log_path.is_none() {
+                anyhow::bail!("crash #{id}: no log available");
+            }
+            vec![crash]
+        }
+        None => db.crashes_with_logs()?,
+    };
+
+    let mut results: Vec<serde_json::Value> = Vec::new();
+    let mut failed: Vec<i64> = Vec::new();
+    for crash in &crashes {
+        let Some(ref p) = crash.log_path else {
+            continue;
+        };
+        let path = Path::new(p);
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read crash log: {p}"))?;
+        let out = match symbolicate_log(db, symbolicator, crash, path, &text) {
+            Ok(out) => out,
+            Err(e) => {
+                warn!(id = crash.id, err = %e, "failed to symbolicate crash log");
+                failed.push(crash.id);
+                continue;
+            }
+        };
+        parse_and_store(db, crash, &out.text)?;
+
+        let out_path = db
+            .get_crash(crash.id)?
+            .and_then(|c| c.symbolicated_log_path);
+        match fmt {
+            Format::Text => {
+                eprintln!(
+                    "  #{:<4} {} frame(s) resolved, {} unresolved → {}",
+                    crash.id,
+                    out.resolved,
+                    out.unresolved,
+                    out_path.as_deref().unwrap_or("?")
+                );
+                for uuid in &out.missing_dsyms {
+                    eprintln!("          no dSYM for image {uuid}");
+                }
+            }
+            Format::Json => results.push(serde_json::json!({
+                "id": crash.id,
+                "symbolicated_log_path": out_path,
+                "resolved": out.resolved,
+                "unresolved": out.unresolved,
+                "missing_dsyms": out.missing_dsyms,
+            })),
+        }
+    }
+    db.prune_empty_groups()?;
+
+    match fmt {
+        Format::Json => {
+            let out = serde_json::json!({
+                "symbolicated": results,
+                "failed": failed,
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            eprintln!(
+                "{} log(s) symbolicated, {} failed",
+                crashes.len() - failed.len(),
+                failed.len()
+            );
+        }
+    }
+    Ok(())
+}
+
 // ─── log (just prints the path) ──────────────────────────────────────────────
 
 fn cmd_log(db: &CrashDb, id: i64) -> Result<()> {
@@ -879,18 +2224,64 @@ fn cmd_log(db: &CrashDb, id: i64) -> Result<()> {
     }
 }
 
+// ─── search ──────────────────────────────────────────────────────────────────
+
+fn cmd_search(db: &CrashDb, query: &str, filters: &db::SearchFilters, fmt: &Format) -> Result<()> {
+    let hits = db.search(query, filters)?;
+
+    match fmt {
+        Format::Json => {
+            let out = serde_json::json!({
+                "results": hits,
+                "count": hits.len(),
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            if hits.is_empty() {
+                println!("No matches.");
+                return Ok(());
+            }
+            println!(
+                " {:<9} {:<5} {:<14} {:<11} {:<30} MATCH",
+                "TYPE", "ID", "STATUS", "DATE", "APP"
+            );
+            println!("{}", "-".repeat(110));
+            for h in &hits {
+                println!(
+                    " {:<9} {:<5} {:<14} {:<11} {:<30} {}",
+                    h.entity,
+                    h.id,
+                    h.status,
+                    h.created_at.get(..10).unwrap_or(&h.created_at),
+                    h.app_bundle_id.as_deref().unwrap_or("-"),
+                    h.snippet,
+                );
+            }
+            println!();
+            println!("{} match(es)", hits.len());
+        }
+    }
+    Ok(())
+}
+
 // ─── status changes ──────────────────────────────────────────────────────────
 
 fn cmd_status(
     db: &CrashDb,
+    wf: &config::WorkflowConfig,
     id: i64,
     status: &str,
     notes: Option<&str>,
+    actor: &str,
     fmt: &Format,
 ) -> Result<()> {
-    if !db.update_status(id, status, notes)? {
-        anyhow::bail!("crash #{id} not found");
-    }
+    let current = db
+        .get_crash(id)?
+        .with_context(|| format!("crash #{id} not found"))?;
+    wf.check_transition(&current.status, status)
+        .with_context(|| format!("crash #{id}"))?;
+    db.update_status(id, status, notes, actor)?;
     let crash = db.get_crash(id)?.unwrap();
     match fmt {
         Format::Json => println!("{}", serde_json::to_string_pretty(&crash)?),
@@ -899,12 +2290,22 @@ fn cmd_status(
     Ok(())
 }
 
-fn cmd_duplicate(db: &CrashDb, id: i64, of_id: i64, fmt: &Format) -> Result<()> {
+fn cmd_duplicate(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    id: i64,
+    of_id: i64,
+    actor: &str,
+    fmt: &Format,
+) -> Result<()> {
+    let current = db
+        .get_crash(id)?
+        .with_context(|| format!("crash #{id} not found"))?;
     db.get_crash(of_id)?
         .with_context(|| format!("target crash #{of_id} not found"))?;
-    if !db.mark_duplicate(id, of_id)? {
-        anyhow::bail!("crash #{id} not found");
-    }
+    wf.check_transition(&current.status, "duplicate")
+        .with_context(|| format!("crash #{id}"))?;
+    db.mark_duplicate(id, of_id, None, actor)?;
     let crash = db.get_crash(id)?.unwrap();
     match fmt {
         Format::Json => println!("{}", serde_json::to_string_pretty(&crash)?),
@@ -913,10 +2314,19 @@ fn cmd_duplicate(db: &CrashDb, id: i64, of_id: i64, fmt: &Format) -> Result<()>
     Ok(())
 }
 
-fn cmd_reopen(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
-    if !db.reopen(id)? {
-        anyhow::bail!("crash #{id} not found");
-    }
+fn cmd_reopen(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    id: i64,
+    actor: &str,
+    fmt: &Format,
+) -> Result<()> {
+    let current = db
+        .get_crash(id)?
+        .with_context(|| format!("crash #{id} not found"))?;
+    wf.check_transition(&current.status, "new")
+        .with_context(|| format!("crash #{id}"))?;
+    db.reopen(id, actor)?;
     let crash = db.get_crash(id)?.unwrap();
     match fmt {
         Format::Json => println!("{}", serde_json::to_string_pretty(&crash)?),
@@ -925,12 +2335,219 @@ fn cmd_reopen(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
     Ok(())
 }
 
+// ─── assignment ──────────────────────────────────────────────────────────────
+
+/// `assign` / `feedback assign`: set or clear who owns a crash or feedback.
+fn cmd_assign(db: &CrashDb, entity: &str, id: i64, to: Option<&str>, fmt: &Format) -> Result<()> {
+    let to = to.map(str::trim);
+    if to == Some("") {
+        anyhow::bail!("assignee is empty; use --none to clear it");
+    }
+    if !db.set_assignee(entity, id, to)? {
+        anyhow::bail!("{entity} #{id} not found");
+    }
+    match fmt {
+        Format::Json => {
+            let row = if entity == "crash" {
+                serde_json::to_value(db.get_crash(id)?)?
+            } else {
+                serde_json::to_value(db.get_feedback(id)?)?
+            };
+            println!("{}", serde_json::to_string_pretty(&row)?);
+        }
+        Format::Text => {
+            let label = if entity == "crash" {
+                "Crash"
+            } else {
+                "Feedback"
+            };
+            match to {
+                Some(who) => eprintln!("{label} #{id} assigned to {who}"),
+                None => eprintln!("{label} #{id} unassigned"),
+            }
+        }
+    }
+    Ok(())
+}
+
+// ─── tags ────────────────────────────────────────────────────────────────────
+
+/// `tag` / `untag` (and their `feedback` forms): add or remove labels.
+fn cmd_tag(
+    db: &CrashDb,
+    entity: &str,
+    id: i64,
+    tags: &[String],
+    add: bool,
+    fmt: &Format,
+) -> Result<()> {
+    let tags = parse_tags(tags)?;
+    let found = if add {
+        db.add_tags(entity, id, &tags)?
+    } else {
+        db.remove_tags(entity, id, &tags)?
+    };
+    if !found {
+        anyhow::bail!("{entity} #{id} not found");
+    }
+    let row = if entity == "crash" {
+        serde_json::to_value(db.get_crash(id)?)?
+    } else {
+        serde_json::to_value(db.get_feedback(id)?)?
+    };
+    match fmt {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&row)?),
+        Format::Text => {
+            let label = if entity == "crash" {
+                "Crash"
+            } else {
+                "Feedback"
+            };
+            match row["tags"].as_array().filter(|t| !t.is_empty()) {
+                Some(tags) => {
+                    let tags: Vec<&str> = tags.iter().filter_map(|t| t.as_str()).collect();
+                    eprintln!("{label} #{id} tags: {}", tags.join(", "));
+                }
+                None => eprintln!("{label} #{id} has no tags"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Normalize tag arguments: split on commas, trim, lowercase. Tags can't be
+/// empty or contain whitespace.
+fn parse_tags(args: &[String]) -> Result<Vec<String>> {
+    let mut tags = Vec::new();
+    for tag in args.iter().flat_map(|a| a.split(',')) {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            continue;
+        }
+        if tag.chars().any(char::is_whitespace) {
+            anyhow::bail!("invalid tag '{tag}': tags can't contain spaces");
+        }
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    if tags.is_empty() && !args.is_empty() {
+        anyhow::bail!("no tags given");
+    }
+    Ok(tags)
+}
+
+// ─── notes ───────────────────────────────────────────────────────────────────
+
+/// `note` / `feedback note`: append a comment to a crash or feedback.
+fn cmd_note(
+    db: &CrashDb,
+    entity: &str,
+    id: i64,
+    text: &str,
+    actor: &str,
+    fmt: &Format,
+) -> Result<()> {
+    if text.trim().is_empty() {
+        anyhow::bail!("note text is empty");
+    }
+    let comment = db
+        .add_comment(entity, id, text, actor)?
+        .with_context(|| format!("{entity} #{id} not found"))?;
+    match fmt {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&comment)?),
+        Format::Text => eprintln!("Note added to {entity} #{id}"),
+    }
+    Ok(())
+}
+
 // ─── stats ───────────────────────────────────────────────────────────────────
 
-fn cmd_stats(db: &CrashDb, app: Option<&str>, fmt: &Format) -> Result<()> {
+/// One line of `stats` in tabular formats.
+#[derive(serde::Serialize)]
+struct StatRow<'a> {
+    section: &'a str,
+    value: Option<&'a str>,
+    count: i64,
+}
+
+const STAT_COLUMNS: &[&str] = &["section", "value", "count"];
+
+/// Flatten stats into `section, value, count` rows: the total, each status,
+/// the unfixed count, then every breakdown in `sections`.
+fn stat_rows<'a>(
+    wf: &'a config::WorkflowConfig,
+    total: i64,
+    by_status: &'a HashMap<String, i64>,
+    unfixed: i64,
+    sections: &[(&'a str, &'a Vec<(String, i64)>)],
+) -> Vec<StatRow<'a>> {
+    let row = |section, value, count| StatRow {
+        section,
+        value,
+        count,
+    };
+    let mut rows = vec![row("total", None, total)];
+    for (status, n) in status_counts(wf, by_status) {
+        rows.push(row("status", Some(status), n));
+    }
+    rows.push(row("unfixed", None, unfixed));
+    for (section, counts) in sections {
+        for (value, n) in counts.iter() {
+            rows.push(row(section, Some(value.as_str()), *n));
+        }
+    }
+    rows
+}
+
+/// Non-zero status counts in workflow order, then any statuses left over
+/// from an older workflow.
+fn status_counts<'a>(
+    wf: &'a config::WorkflowConfig,
+    by_status: &'a HashMap<String, i64>,
+) -> Vec<(&'a str, i64)> {
+    let mut out: Vec<(&str, i64)> = wf
+        .statuses
+        .iter()
+        .filter_map(|s| Some((s.as_str(), *by_status.get(s)?)))
+        .collect();
+    let mut legacy: Vec<(&str, i64)> = by_status
+        .iter()
+        .filter(|(s, _)| !wf.is_status(s))
+        .map(|(s, n)| (s.as_str(), *n))
+        .collect();
+    legacy.sort();
+    out.extend(legacy);
+    out.retain(|(_, n)| *n > 0);
+    out
+}
+
+fn cmd_stats(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    app: Option<&str>,
+    out: OutputFormat,
+) -> Result<()> {
     let stats = db.stats(app)?;
 
-    match fmt {
+    if let Some(t) = out.table() {
+        let rows = stat_rows(
+            wf,
+            stats.total,
+            &stats.by_status,
+            stats.unfixed,
+            &[
+                ("device", &stats.by_device),
+                ("os", &stats.by_os),
+                ("exception", &stats.by_exception),
+                ("build", &stats.by_build),
+                ("tag", &stats.by_tag),
+                ("open_by_assignee", &stats.by_assignee),
+            ],
+        );
+        return print_table(t, STAT_COLUMNS, &rows);
+    }
+    match out.base() {
         Format::Json => {
             println!("{}", serde_json::to_string_pretty(&stats)?);
         }
@@ -938,11 +2555,8 @@ fn cmd_stats(db: &CrashDb, app: Option<&str>, fmt: &Format) -> Result<()> {
             println!("Crash Statistics");
             println!("{}", "─".repeat(30));
             println!("Total:          {}", stats.total);
-            for status in &["new", "investigating", "fixed", "wontfix", "duplicate"] {
-                let n = stats.by_status.get(*status).copied().unwrap_or(0);
-                if n > 0 {
-                    println!("{:<16}{}", format!("{status}:"), n);
-                }
+            for (status, n) in status_counts(wf, &stats.by_status) {
+                println!("{:<16}{}", format!("{status}:"), n);
             }
             println!("Unfixed:        {}", stats.unfixed);
 
@@ -954,37 +2568,671 @@ fn cmd_stats(db: &CrashDb, app: Option<&str>, fmt: &Format) -> Result<()> {
                 }
             }
 
-            if !stats.by_os.is_empty() {
-                println!();
-                println!("By OS:");
-                for (os, count) in &stats.by_os {
-                    println!("  {:<20} {count}", os);
+            if !stats.by_os.is_empty() {
+                println!();
+                println!("By OS:");
+                for (os, count) in &stats.by_os {
+                    println!("  {:<20} {count}", os);
+                }
+            }
+
+            if !stats.by_exception.is_empty() {
+                println!();
+                println!("By Exception:");
+                for (exception, count) in &stats.by_exception {
+                    println!("  {:<20} {count}", exception);
+                }
+            }
+
+            if !stats.by_build.is_empty() {
+                println!();
+                println!("By Build:");
+                for (build, count) in &stats.by_build {
+                    println!("  {:<20} {count}", build);
+                }
+            }
+
+            if !stats.by_tag.is_empty() {
+                println!();
+                println!("By Tag:");
+                for (tag, count) in &stats.by_tag {
+                    println!("  {:<20} {count}", tag);
+                }
+            }
+
+            // Skipped until someone has been assigned something.
+            if stats
+                .by_assignee
+                .iter()
+                .any(|(who, _)| who != "(unassigned)")
+            {
+                println!();
+                println!("Open by Assignee:");
+                for (who, count) in &stats.by_assignee {
+                    println!("  {:<20} {count}", who);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// ─── groups ──────────────────────────────────────────────────────────────────
+
+/// Every `GroupRow` field, in order, for `groups list --format csv|ndjson`.
+const GROUP_COLUMNS: &[&str] = &[
+    "id",
+    "app_id",
+    "hash",
+    "signature",
+    "exception_type",
+    "frames",
+    "created_at",
+    "crash_count",
+    "unfixed",
+    "first_seen",
+    "last_seen",
+    "build_count",
+    "device_count",
+    "status",
+    "fixed_build_id",
+    "fixed_at",
+    "fix_notes",
+    "app_bundle_id",
+];
+
+/// Columns for `groups list --format markdown`.
+const GROUP_SUMMARY_COLUMNS: &[&str] = &[
+    "id",
+    "status",
+    "crash_count",
+    "unfixed",
+    "first_seen",
+    "last_seen",
+    "exception_type",
+    "frames",
+];
+
+fn cmd_groups_list(db: &CrashDb, filters: &GroupFilters, out: OutputFormat) -> Result<()> {
+    let groups = db.list_groups(filters)?;
+
+    if let Some(t) = out.table() {
+        return print_table(t, t.columns(GROUP_COLUMNS, GROUP_SUMMARY_COLUMNS), &groups);
+    }
+    match out.base() {
+        Format::Json => {
+            let out = serde_json::json!({
+                "groups": groups,
+                "count": groups.len(),
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            if groups.is_empty() {
+                println!("No crash groups found.");
+                return Ok(());
+            }
+            println!(
+                " {:<5} {:<10} {:<7} {:<7} {:<11} {:<11} {:<6} {:<7} SIGNATURE",
+                "ID",
+                "STATUS",
+                "CRASHES",
+                "UNFIXED",
+                "FIRST SEEN",
+                "LAST SEEN",
+                "BUILDS",
+                "DEVICES"
+            );
+            println!("{}", "-".repeat(120));
+            for g in &groups {
+                println!(
+                    " {:<5} {:<10} {:<7} {:<7} {:<11} {:<11} {:<6} {:<7} {}",
+                    g.id,
+                    g.status,
+                    g.crash_count,
+                    g.unfixed,
+                    g.first_seen
+                        .as_deref()
+                        .map(|d| &d[..10.min(d.len())])
+                        .unwrap_or("-"),
+                    g.last_seen
+                        .as_deref()
+                        .map(|d| &d[..10.min(d.len())])
+                        .unwrap_or("-"),
+                    g.build_count,
+                    g.device_count,
+                    group_summary(g),
+                );
+            }
+            println!();
+            println!("{} group(s) shown", groups.len());
+        }
+    }
+    Ok(())
+}
+
+fn cmd_groups_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
+    let group = db
+        .get_group(id)?
+        .with_context(|| format!("group #{id} not found"))?;
+    let breakdown = db.group_breakdown(id)?;
+    let crashes = db.list_crashes(&CrashFilters {
+        status: None,
+        since: None,
+        app_bundle_id: None,
+        exception_type: None,
+        group_id: Some(id),
+        regressions_only: false,
+        include_expired: true,
+        only_expired: false,
+        assignee: None,
+        unassigned: false,
+        tags: Vec::new(),
+        all_tags: false,
+        query: ListQuery::default(),
+        limit: group.crash_count.max(1) as usize,
+    })?;
+
+    match fmt {
+        Format::Json => {
+            let mut out = serde_json::to_value(&group)?;
+            out["by_build"] = serde_json::to_value(&breakdown.by_build)?;
+            out["by_device"] = serde_json::to_value(&breakdown.by_device)?;
+            out["by_os"] = serde_json::to_value(&breakdown.by_os)?;
+            out["crashes"] = serde_json::to_value(&crashes)?;
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            println!("Group #{}", group.id);
+            println!("{}", "─".repeat(40));
+            println!("Hash:       {}", group.hash);
+            println!("Signature:  {}", group.signature);
+            println!("Status:     {}", group.status);
+            if let Some(ref v) = group.fixed_build_id {
+                println!("Fixed In:   build {v}");
+            }
+            if let Some(ref v) = group.fix_notes {
+                println!("Fix Notes:  {v}");
+            }
+            if let Some(ref v) = group.exception_type {
+                println!("Exception:  {v}");
+            }
+            if let Some(ref v) = group.app_bundle_id {
+                println!("App:        {v}");
+            }
+            println!(
+                "Crashes:    {} ({} unfixed)",
+                group.crash_count, group.unfixed
+            );
+            if let Some(ref v) = group.first_seen {
+                println!("First Seen: {v}");
+            }
+            if let Some(ref v) = group.last_seen {
+                println!("Last Seen:  {v}");
+            }
+            if !group.frames.is_empty() {
+                println!();
+                println!("Top Frames:");
+                for f in &group.frames {
+                    println!("  {f}");
+                }
+            }
+            for (title, rows) in [
+                ("By Build:", &breakdown.by_build),
+                ("By Device:", &breakdown.by_device),
+                ("By OS:", &breakdown.by_os),
+            ] {
+                if !rows.is_empty() {
+                    println!();
+                    println!("{title}");
+                    for (key, count) in rows {
+                        println!("  {:<20} {count}", key);
+                    }
+                }
+            }
+            println!();
+            println!("Crashes:");
+            for c in &crashes {
+                println!(
+                    "  #{:<5} {:<14} {}  {} / {}",
+                    c.id,
+                    c.status,
+                    &c.created_at[..19.min(c.created_at.len())],
+                    c.device_model.as_deref().unwrap_or("?"),
+                    c.os_version.as_deref().unwrap_or("?"),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_groups_rebuild(db: &CrashDb, fmt: &Format) -> Result<()> {
+    let mut grouped = 0;
+    for crash in db.crashes_with_logs()? {
+        if let Some(report) = db.get_report(crash.id)? {
+            if assign_group(db, &crash, &report)?.is_some() {
+                grouped += 1;
+            }
+        }
+    }
+    let pruned = db.prune_empty_groups()?;
+
+    match fmt {
+        Format::Json => {
+            let out = serde_json::json!({
+                "grouped": grouped,
+                "pruned_groups": pruned,
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            eprintln!("{grouped} crash(es) grouped, {pruned} empty group(s) removed");
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_groups_fix(
+    cfg: &config::Config,
+    db: &CrashDb,
+    id: i64,
+    build: &str,
+    notes: Option<&str>,
+    actor: &str,
+    fmt: &Format,
+) -> Result<()> {
+    let group = db
+        .get_group(id)?
+        .with_context(|| format!("group #{id} not found"))?;
+    require_build(cfg, db, group.app_id, build).await?;
+    let closed = db
+        .fix_group(id, build, notes, actor)?
+        .with_context(|| format!("group #{id} not found"))?;
+    let group = db.get_group(id)?.unwrap();
+    match fmt {
+        Format::Json => {
+            let mut out = serde_json::to_value(&group)?;
+            out["crashes_closed"] = closed.into();
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            eprintln!("Group #{id} marked as fixed in build {build} ({closed} crash(es) closed)")
+        }
+    }
+    Ok(())
+}
+
+fn cmd_groups_reopen(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
+    if !db.reopen_group(id)? {
+        anyhow::bail!("group #{id} not found");
+    }
+    let group = db.get_group(id)?.unwrap();
+    match fmt {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&group)?),
+        Format::Text => eprintln!("Group #{id} reopened"),
+    }
+    Ok(())
+}
+
+/// Make sure later syncs can order crashes against fix build `build_id`:
+/// it must have an upload date or crashes/feedback of its own. Otherwise its
+/// metadata is fetched, so typos fail here instead of silently leaving every
+/// later crash in the group untriaged.
+async fn require_build(
+    cfg: &config::Config,
+    db: &CrashDb,
+    app_id: i64,
+    build_id: &str,
+) -> Result<()> {
+    let uploaded = db.get_build(build_id)?.and_then(|b| b.uploaded_at);
+    if uploaded.is_some() || db.build_first_seen(build_id)?.is_some() {
+        return Ok(());
+    }
+    info!(build_id, "fetching build");
+    let resp = make_client(cfg)?
+        .get_build(build_id)
+        .await
+        .with_context(|| format!("could not look up build '{build_id}'"))?
+        .with_context(|| {
+            format!(
+                "build '{build_id}' not found on App Store Connect; \
+                 pass the ASC build ID (see `builds report`)"
+            )
+        })?;
+    let build = new_build(app_id, resp);
+    if build.uploaded_at.is_none() {
+        anyhow::bail!("build '{build_id}' has no upload date to order crashes against");
+    }
+    db.upsert_build(&build)
+}
+
+/// What happened to a newly synced crash whose group was already fixed.
+enum GroupOutcome {
+    Regression {
+        group_id: i64,
+        fixed_build: String,
+    },
+    Duplicate {
+        group_id: i64,
+        of_id: i64,
+        fixed_build: String,
+    },
+    /// The crash's build is unknown or can't be ordered against the fix
+    /// build, so it was left as is.
+    Untriaged {
+        group_id: i64,
+        fixed_build: String,
+    },
+}
+
+/// If a new crash lands in a group that was marked fixed, flag it as a
+/// regression when it comes from the fix build or later, or link it as a
+/// duplicate when it comes from an older build that predates the fix.
+fn apply_group_status(db: &CrashDb, crash: &CrashRow) -> Result<Option<GroupOutcome>> {
+    if crash.status != "new" {
+        return Ok(None);
+    }
+    let Some(group_id) = crash.group_id else {
+        return Ok(None);
+    };
+    let Some(group) = db.get_group(group_id)? else {
+        return Ok(None);
+    };
+    let (true, Some(fixed_build)) = (group.status == "fixed", group.fixed_build_id) else {
+        return Ok(None);
+    };
+    let order = match crash.build_id {
+        Some(ref build) => db.compare_builds(build, &fixed_build)?,
+        None => None,
+    };
+
+    match order {
+        Some(std::cmp::Ordering::Less) => {
+            let Some(of_id) = db.group_primary_crash(group_id, crash.id)? else {
+                return Ok(None);
+            };
+            let notes = format!("older build than group #{group_id}'s fix ({fixed_build})");
+            db.mark_duplicate(crash.id, of_id, Some(&notes), "sync")?;
+            Ok(Some(GroupOutcome::Duplicate {
+                group_id,
+                of_id,
+                fixed_build,
+            }))
+        }
+        Some(_) => {
+            db.mark_regression(crash.id, group_id)?;
+            db.set_group_status(group_id, "regressed")?;
+            Ok(Some(GroupOutcome::Regression {
+                group_id,
+                fixed_build,
+            }))
+        }
+        None => {
+            warn!(
+                crash = crash.id,
+                group = group_id,
+                build = crash.build_id.as_deref().unwrap_or("unknown"),
+                fixed_build,
+                "can't tell whether crash predates its group's fix; left untriaged"
+            );
+            Ok(Some(GroupOutcome::Untriaged {
+                group_id,
+                fixed_build,
+            }))
+        }
+    }
+}
+
+/// One-line description of a group: exception plus its top frame.
+fn group_summary(g: &db::GroupRow) -> String {
+    let exception = g.exception_type.as_deref().unwrap_or("UNKNOWN");
+    match g.frames.first() {
+        Some(f) => format!("{exception} @ {f}"),
+        None => exception.to_string(),
+    }
+}
+
+// ─── builds report ───────────────────────────────────────────────────────────
+
+/// One build in `builds report`.
+#[derive(serde::Serialize)]
+struct BuildReport {
+    build_id: String,
+    label: String,
+    version: Option<String>,
+    build_number: Option<String>,
+    uploaded_at: Option<String>,
+    first_seen: String,
+    /// The closest earlier build with crashes; signatures are compared
+    /// against it. None for the first such build and for builds without
+    /// crashes.
+    previous_build_id: Option<String>,
+    crash_count: i64,
+    crashes_per_day: Option<f64>,
+    feedback_count: i64,
+    signature_count: usize,
+    new_signatures: Vec<SignatureRef>,
+    disappeared_signatures: Vec<SignatureRef>,
+    /// Groups seen in this build that were marked fixed in it or earlier.
+    regressed_signatures: Vec<SignatureRef>,
+    /// Crashes `sync` flagged as regressions of a fixed group.
+    regression_count: i64,
+    regression: bool,
+}
+
+#[derive(serde::Serialize)]
+struct SignatureRef {
+    group_id: i64,
+    hash: String,
+    signature: String,
+    summary: String,
+}
+
+fn cmd_builds_report(
+    cfg: &config::Config,
+    db: &CrashDb,
+    app: Option<&str>,
+    limit: usize,
+    fmt: &Format,
+) -> Result<()> {
+    let bundle_id = match app {
+        Some(a) => a,
+        None => match cfg.apps.as_slice() {
+            [only] => only.bundle_id.as_str(),
+            _ => anyhow::bail!("--app is required when more than one app is configured"),
+        },
+    };
+    let app_id = db
+        .find_app_id(bundle_id)?
+        .with_context(|| format!("app '{bundle_id}' not found in database; run sync first"))?;
+
+    let activity = db.build_activity(app_id)?;
+    let mut groups: HashMap<i64, Option<db::GroupRow>> = HashMap::new();
+    let mut group = |id: i64| -> Result<Option<db::GroupRow>> {
+        if let Some(g) = groups.get(&id) {
+            return Ok(g.clone());
+        }
+        let g = db.get_group(id)?;
+        groups.insert(id, g.clone());
+        Ok(g)
+    };
+
+    let now = chrono::Utc::now().fixed_offset();
+    let mut builds: Vec<BuildReport> = Vec::new();
+    let mut prev: Option<&db::BuildActivity> = None;
+    for (i, b) in activity.iter().enumerate() {
+        // Builds with only feedback say nothing about which signatures
+        // went away, so they're neither compared nor compared against.
+        let compared = prev.filter(|_| b.crash_count > 0);
+        let current: BTreeSet<i64> = b.group_ids.iter().copied().collect();
+        let previous: BTreeSet<i64> = compared
+            .map(|p| p.group_ids.iter().copied().collect())
+            .unwrap_or_default();
+        let (mut new, mut gone) = (Vec::new(), Vec::new());
+        if compared.is_some() {
+            for id in current.difference(&previous) {
+                new.extend(group(*id)?);
+            }
+            for id in previous.difference(&current) {
+                gone.extend(group(*id)?);
+            }
+        }
+        let mut regressed = Vec::new();
+        for id in &current {
+            let Some(g) = group(*id)? else { continue };
+            let Some(ref fixed_build) = g.fixed_build_id else {
+                continue;
+            };
+            if matches!(
+                db.compare_builds(&b.build_id, fixed_build)?,
+                Some(std::cmp::Ordering::Equal | std::cmp::Ordering::Greater)
+            ) {
+                regressed.push(g);
+            }
+        }
+
+        // Crashes per day while this was the newest build.
+        let end = activity.get(i + 1).map_or(Some(now), build_start);
+        let per_day = match (build_start(b), end) {
+            (Some(start), Some(end)) => {
+                let days = ((end - start).num_seconds() as f64 / 86_400.0).max(1.0);
+                Some((b.crash_count as f64 / days * 100.0).round() / 100.0)
+            }
+            _ => None,
+        };
+
+        let refs = |groups: Vec<db::GroupRow>| -> Vec<SignatureRef> {
+            groups
+                .into_iter()
+                .map(|g| SignatureRef {
+                    group_id: g.id,
+                    summary: group_summary(&g),
+                    hash: g.hash,
+                    signature: g.signature,
+                })
+                .collect()
+        };
+        builds.push(BuildReport {
+            build_id: b.build_id.clone(),
+            label: db::build_label(b.version.as_deref(), b.build_number.as_deref())
+                .unwrap_or_else(|| b.build_id.clone()),
+            version: b.version.clone(),
+            build_number: b.build_number.clone(),
+            uploaded_at: b.uploaded_at.clone(),
+            first_seen: b.first_seen.clone(),
+            previous_build_id: compared.map(|p| p.build_id.clone()),
+            crash_count: b.crash_count,
+            crashes_per_day: per_day,
+            feedback_count: b.feedback_count,
+            signature_count: current.len(),
+            new_signatures: refs(new),
+            disappeared_signatures: refs(gone),
+            regression: !regressed.is_empty() || b.regression_count > 0,
+            regressed_signatures: refs(regressed),
+            regression_count: b.regression_count,
+        });
+        if b.crash_count > 0 {
+            prev = Some(b);
+        }
+    }
+    // Newest first, keeping comparisons against the real predecessor.
+    builds.reverse();
+    builds.truncate(limit);
+
+    match fmt {
+        Format::Json => {
+            let out = serde_json::json!({
+                "app": bundle_id,
+                "builds": builds,
+                "count": builds.len(),
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            if builds.is_empty() {
+                println!("No builds found.");
+                return Ok(());
+            }
+            println!(
+                " {:<16} {:<11} {:>7} {:>7} {:>8} {:>5} {:>4} {:>4}",
+                "BUILD", "UPLOADED", "CRASHES", "PER DAY", "FEEDBACK", "SIGS", "NEW", "GONE"
+            );
+            println!("{}", "-".repeat(90));
+            for b in &builds {
+                let date = b.uploaded_at.as_deref().unwrap_or(&b.first_seen);
+                let per_day = b
+                    .crashes_per_day
+                    .map(|v| format!("{v:.2}"))
+                    .unwrap_or_else(|| "-".into());
+                println!(
+                    " {:<16} {:<11} {:>7} {:>7} {:>8} {:>5} {:>4} {:>4}{}",
+                    b.label,
+                    &date[..10.min(date.len())],
+                    b.crash_count,
+                    per_day,
+                    b.feedback_count,
+                    b.signature_count,
+                    b.new_signatures.len(),
+                    b.disappeared_signatures.len(),
+                    if b.regression { "  REGRESSION" } else { "" },
+                );
+                for (mark, sigs) in [
+                    ("+", &b.new_signatures),
+                    ("-", &b.disappeared_signatures),
+                    ("!", &b.regressed_signatures),
+                ] {
+                    for sig in sigs {
+                        println!("     {mark} #{:<4} {}", sig.group_id, sig.summary);
+                    }
                 }
             }
+            println!();
+            println!("{} build(s) shown", builds.len());
         }
     }
     Ok(())
 }
 
+/// When a build became available: its upload date, or the first crash or
+/// feedback seen from it when metadata is missing.
+fn build_start(b: &db::BuildActivity) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let date = b.uploaded_at.as_deref().unwrap_or(&b.first_seen);
+    chrono::DateTime::parse_from_rfc3339(date).ok()
+}
+
 // ─── feedback list ────────────────────────────────────────────────────────────
 
+/// Columns for `feedback list --format markdown`.
+const FEEDBACK_SUMMARY_COLUMNS: &[&str] = &[
+    "id",
+    "status",
+    "created_at",
+    "device_model",
+    "os_version",
+    "build",
+    "tester_comment",
+    "app_bundle_id",
+];
+
 fn cmd_feedback_list(
     db: &CrashDb,
-    status: Option<String>,
-    since: Option<String>,
-    app: Option<String>,
-    limit: usize,
-    fmt: &Format,
+    wf: &config::WorkflowConfig,
+    filters: &FeedbackFilters,
+    columns: &[String],
+    out: OutputFormat,
 ) -> Result<()> {
-    let filters = FeedbackFilters {
-        status: status.map(|s| s.split(',').map(|x| x.trim().to_string()).collect()),
-        since,
-        app_bundle_id: app,
-        limit,
-    };
-    let feedbacks = db.list_feedbacks(&filters)?;
+    check_columns(db::FEEDBACK_FIELDS, columns)?;
+    let feedbacks = db.list_feedbacks(filters)?;
 
-    match fmt {
+    if !columns.is_empty() {
+        return print_columns("feedbacks", columns, &feedbacks, out);
+    }
+    if let Some(t) = out.table() {
+        let all = field_names(db::FEEDBACK_FIELDS);
+        return print_table(t, t.columns(&all, FEEDBACK_SUMMARY_COLUMNS), &feedbacks);
+    }
+    match out.base() {
         Format::Json => {
             let out = serde_json::json!({
                 "feedbacks": feedbacks,
@@ -998,10 +3246,10 @@ fn cmd_feedback_list(
                 return Ok(());
             }
             println!(
-                " {:<5} {:<14} {:<20} {:<14} {:<10} APP",
-                "ID", "STATUS", "DATE", "DEVICE", "OS"
+                " {:<5} {:<14} {:<20} {:<14} {:<10} {:<14} APP",
+                "ID", "STATUS", "DATE", "DEVICE", "OS", "BUILD"
             );
-            println!("{}", "-".repeat(90));
+            println!("{}", "-".repeat(105));
             for f in &feedbacks {
                 let date = if f.created_at.len() >= 19 {
                     &f.created_at[..19]
@@ -1009,21 +3257,25 @@ fn cmd_feedback_list(
                     &f.created_at
                 };
                 println!(
-                    " {:<5} {:<14} {:<20} {:<14} {:<10} {}",
+                    "{}{:<5} {:<14} {:<20} {:<14} {:<10} {:<14} {}",
+                    expired_marker(f.remote_deleted_at.as_deref()),
                     f.id,
                     f.status,
                     date,
                     f.device_model.as_deref().unwrap_or("-"),
                     f.os_version.as_deref().unwrap_or("-"),
+                    db::build_label(f.app_version.as_deref(), f.build_number.as_deref())
+                        .as_deref()
+                        .unwrap_or("-"),
                     f.app_bundle_id.as_deref().unwrap_or("-"),
                 );
             }
             println!();
-            let unfixed = feedbacks
-                .iter()
-                .filter(|f| f.status == "new" || f.status == "investigating")
-                .count();
+            let unfixed = feedbacks.iter().filter(|f| wf.is_open(&f.status)).count();
             println!("{} feedback(s) shown ({unfixed} unfixed)", feedbacks.len());
+            if feedbacks.iter().any(|f| f.remote_deleted_at.is_some()) {
+                println!("* no longer on App Store Connect (local copy only)");
+            }
         }
     }
     Ok(())
@@ -1035,17 +3287,31 @@ fn cmd_feedback_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
     let feedback = db
         .get_feedback(id)?
         .with_context(|| format!("feedback #{id} not found"))?;
+    let history = db.status_events("feedback", id)?;
+    let comments = db.comments("feedback", id)?;
 
     match fmt {
         Format::Json => {
-            println!("{}", serde_json::to_string_pretty(&feedback)?);
+            let mut out = serde_json::to_value(&feedback)?;
+            out["history"] = serde_json::to_value(&history)?;
+            out["comments"] = serde_json::to_value(&comments)?;
+            println!("{}", serde_json::to_string_pretty(&out)?);
         }
         Format::Text => {
             println!("Feedback #{}", feedback.id);
             println!("{}", "─".repeat(40));
             println!("Status:     {}", feedback.status);
+            if let Some(ref v) = feedback.assignee {
+                println!("Assignee:   {v}");
+            }
+            if !feedback.tags.is_empty() {
+                println!("Tags:       {}", feedback.tags.join(", "));
+            }
             println!("Created:    {}", feedback.created_at);
             println!("Synced:     {}", feedback.synced_at);
+            if let Some(ref at) = feedback.remote_deleted_at {
+                println!("Remote:     gone from App Store Connect since {at} (local copy only)");
+            }
             if let Some(ref v) = feedback.device_model {
                 println!("Device:     {v}");
             }
@@ -1071,6 +3337,12 @@ fn cmd_feedback_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
             if let Some(ref v) = feedback.app_name {
                 println!("App Name:   {v}");
             }
+            if let Some(v) = db::build_label(
+                feedback.app_version.as_deref(),
+                feedback.build_number.as_deref(),
+            ) {
+                println!("Build:      {v}");
+            }
             if let Some(v) = feedback.battery_pct {
                 println!("Battery:    {v}%");
             }
@@ -1086,6 +3358,8 @@ fn cmd_feedback_show(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
             if let Some(v) = feedback.duplicate_of {
                 println!("Dup Of:     #{v}");
             }
+            print_history(&history);
+            print_comments(&comments);
 
             if let Some(ref p) = feedback.screenshot_path {
                 println!("Screenshot: {p}");
@@ -1122,14 +3396,19 @@ fn cmd_feedback_screenshot(db: &CrashDb, id: i64) -> Result<()> {
 
 fn cmd_feedback_status(
     db: &CrashDb,
+    wf: &config::WorkflowConfig,
     id: i64,
     status: &str,
     notes: Option<&str>,
+    actor: &str,
     fmt: &Format,
 ) -> Result<()> {
-    if !db.update_feedback_status(id, status, notes)? {
-        anyhow::bail!("feedback #{id} not found");
-    }
+    let current = db
+        .get_feedback(id)?
+        .with_context(|| format!("feedback #{id} not found"))?;
+    wf.check_transition(&current.status, status)
+        .with_context(|| format!("feedback #{id}"))?;
+    db.update_feedback_status(id, status, notes, actor)?;
     let feedback = db.get_feedback(id)?.unwrap();
     match fmt {
         Format::Json => println!("{}", serde_json::to_string_pretty(&feedback)?),
@@ -1138,10 +3417,20 @@ fn cmd_feedback_status(
     Ok(())
 }
 
-fn cmd_feedback_duplicate(db: &CrashDb, id: i64, of_id: i64, fmt: &Format) -> Result<()> {
-    if !db.mark_feedback_duplicate(id, of_id)? {
-        anyhow::bail!("feedback #{id} not found");
-    }
+fn cmd_feedback_duplicate(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    id: i64,
+    of_id: i64,
+    actor: &str,
+    fmt: &Format,
+) -> Result<()> {
+    let current = db
+        .get_feedback(id)?
+        .with_context(|| format!("feedback #{id} not found"))?;
+    wf.check_transition(&current.status, "duplicate")
+        .with_context(|| format!("feedback #{id}"))?;
+    db.mark_feedback_duplicate(id, of_id, actor)?;
     let feedback = db.get_feedback(id)?.unwrap();
     match fmt {
         Format::Json => println!("{}", serde_json::to_string_pretty(&feedback)?),
@@ -1150,10 +3439,19 @@ fn cmd_feedback_duplicate(db: &CrashDb, id: i64, of_id: i64, fmt: &Format) -> Re
     Ok(())
 }
 
-fn cmd_feedback_reopen(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
-    if !db.reopen_feedback(id)? {
-        anyhow::bail!("feedback #{id} not found");
-    }
+fn cmd_feedback_reopen(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    id: i64,
+    actor: &str,
+    fmt: &Format,
+) -> Result<()> {
+    let current = db
+        .get_feedback(id)?
+        .with_context(|| format!("feedback #{id} not found"))?;
+    wf.check_transition(&current.status, "new")
+        .with_context(|| format!("feedback #{id}"))?;
+    db.reopen_feedback(id, actor)?;
     let feedback = db.get_feedback(id)?.unwrap();
     match fmt {
         Format::Json => println!("{}", serde_json::to_string_pretty(&feedback)?),
@@ -1164,10 +3462,31 @@ fn cmd_feedback_reopen(db: &CrashDb, id: i64, fmt: &Format) -> Result<()> {
 
 // ─── feedback stats ───────────────────────────────────────────────────────────
 
-fn cmd_feedback_stats(db: &CrashDb, app: Option<&str>, fmt: &Format) -> Result<()> {
+fn cmd_feedback_stats(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    app: Option<&str>,
+    out: OutputFormat,
+) -> Result<()> {
     let stats = db.feedback_stats(app)?;
 
-    match fmt {
+    if let Some(t) = out.table() {
+        let rows = stat_rows(
+            wf,
+            stats.total,
+            &stats.by_status,
+            stats.unfixed,
+            &[
+                ("device", &stats.by_device),
+                ("os", &stats.by_os),
+                ("build", &stats.by_build),
+                ("tag", &stats.by_tag),
+                ("open_by_assignee", &stats.by_assignee),
+            ],
+        );
+        return print_table(t, STAT_COLUMNS, &rows);
+    }
+    match out.base() {
         Format::Json => {
             println!("{}", serde_json::to_string_pretty(&stats)?);
         }
@@ -1175,11 +3494,8 @@ fn cmd_feedback_stats(db: &CrashDb, app: Option<&str>, fmt: &Format) -> Result<(
             println!("Feedback Statistics");
             println!("{}", "─".repeat(30));
             println!("Total:          {}", stats.total);
-            for status in &["new", "investigating", "fixed", "wontfix", "duplicate"] {
-                let n = stats.by_status.get(*status).copied().unwrap_or(0);
-                if n > 0 {
-                    println!("{:<16}{}", format!("{status}:"), n);
-                }
+            for (status, n) in status_counts(wf, &stats.by_status) {
+                println!("{:<16}{}", format!("{status}:"), n);
             }
             println!("Unfixed:        {}", stats.unfixed);
 
@@ -1198,21 +3514,643 @@ fn cmd_feedback_stats(db: &CrashDb, app: Option<&str>, fmt: &Format) -> Result<(
                     println!("  {:<20} {count}", os);
                 }
             }
+
+            if !stats.by_build.is_empty() {
+                println!();
+                println!("By Build:");
+                for (build, count) in &stats.by_build {
+                    println!("  {:<20} {count}", build);
+                }
+            }
+
+            if !stats.by_tag.is_empty() {
+                println!();
+                println!("By Tag:");
+                for (tag, count) in &stats.by_tag {
+                    println!("  {:<20} {count}", tag);
+                }
+            }
+
+            // Skipped until someone has been assigned something.
+            if stats
+                .by_assignee
+                .iter()
+                .any(|(who, _)| who != "(unassigned)")
+            {
+                println!();
+                println!("Open by Assignee:");
+                for (who, count) in &stats.by_assignee {
+                    println!("  {:<20} {count}", who);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// ─── view ────────────────────────────────────────────────────────────────────
+
+fn cmd_view(
+    db: &CrashDb,
+    wf: &config::WorkflowConfig,
+    views: &[config::ViewConfig],
+    name: Option<&str>,
+    limit: Option<usize>,
+    offset: usize,
+    out: OutputFormat,
+) -> Result<()> {
+    let Some(name) = name else {
+        return print_views(views, &out.base());
+    };
+    let Some(view) = views.iter().find(|v| v.name == name) else {
+        if views.is_empty() {
+            anyhow::bail!("unknown view '{name}': no [[views]] in config.toml");
+        }
+        let names: Vec<&str> = views.iter().map(|v| v.name.as_str()).collect();
+        anyhow::bail!("unknown view '{name}'; configured: {}", names.join(", "));
+    };
+
+    let query = ListQuery {
+        filter: view.filter.as_deref().map(query::parse).transpose()?,
+        until: None,
+        build: None,
+        sort: match view.sort {
+            Some(ref sort) => query::parse_sort(sort)?,
+            None => Vec::new(),
+        },
+        offset,
+    };
+    let limit = limit.or(view.limit).unwrap_or(50);
+    let context = || format!("view '{name}'");
+
+    match view.entity {
+        config::ViewEntity::Crashes => {
+            let filters = CrashFilters {
+                status: None,
+                since: None,
+                app_bundle_id: None,
+                exception_type: None,
+                group_id: None,
+                regressions_only: false,
+                include_expired: false,
+                only_expired: false,
+                assignee: None,
+                unassigned: false,
+                tags: Vec::new(),
+                all_tags: false,
+                query,
+                limit,
+            };
+            cmd_list(db, wf, &filters, &view.columns, out).with_context(context)
+        }
+        config::ViewEntity::Feedback => {
+            let filters = FeedbackFilters {
+                status: None,
+                since: None,
+                app_bundle_id: None,
+                include_expired: false,
+                only_expired: false,
+                assignee: None,
+                unassigned: false,
+                tags: Vec::new(),
+                all_tags: false,
+                query,
+                limit,
+            };
+            cmd_feedback_list(db, wf, &filters, &view.columns, out).with_context(context)
+        }
+    }
+}
+
+fn print_views(views: &[config::ViewConfig], fmt: &Format) -> Result<()> {
+    match fmt {
+        Format::Json => {
+            let out = serde_json::json!({
+                "views": views,
+                "count": views.len(),
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            if views.is_empty() {
+                println!("No views configured. Add [[views]] to config.toml.");
+                return Ok(());
+            }
+            println!(" {:<20} {:<9} FILTER", "NAME", "ENTITY");
+            println!("{}", "-".repeat(80));
+            for v in views {
+                let entity = match v.entity {
+                    config::ViewEntity::Crashes => "crashes",
+                    config::ViewEntity::Feedback => "feedback",
+                };
+                println!(
+                    " {:<20} {:<9} {}",
+                    v.name,
+                    entity,
+                    v.filter.as_deref().unwrap_or("-")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Make sure every name in `columns` is one of `fields` or `build`.
+fn check_columns(fields: &[query::Field], columns: &[String]) -> Result<()> {
+    for c in columns {
+        if c != "build" && !fields.iter().any(|f| f.name == c) {
+            let names: Vec<&str> = fields.iter().map(|f| f.name).chain(["build"]).collect();
+            anyhow::bail!(
+                "unknown column '{c}'; expected one of: {}",
+                names.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print `rows` showing only `columns`, already checked by `check_columns`.
+fn print_columns<T: serde::Serialize>(
+    key: &str,
+    columns: &[String],
+    rows: &[T],
+    out: OutputFormat,
+) -> Result<()> {
+    if let Some(t) = out.table() {
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+        return print_table(t, &columns, rows);
+    }
+    let rows: Vec<serde_json::Value> = rows
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<_, _>>()?;
+
+    match out.base() {
+        Format::Json => {
+            // Serialized through maps rather than `json!`, which sorts keys,
+            // to keep the columns in order.
+            use serde::ser::{SerializeMap, Serializer};
+            let projected: Vec<Projection> =
+                rows.iter().map(|row| Projection { columns, row }).collect();
+            let mut stdout = std::io::stdout().lock();
+            let mut json = serde_json::Serializer::pretty(&mut stdout);
+            let mut map = json.serialize_map(Some(2))?;
+            map.serialize_entry("count", &projected.len())?;
+            map.serialize_entry(key, &projected)?;
+            map.end()?;
+            writeln!(stdout)?;
+        }
+        Format::Text => {
+            if rows.is_empty() {
+                println!("No results.");
+                return Ok(());
+            }
+            let cells: Vec<Vec<String>> = rows
+                .iter()
+                .map(|r| {
+                    columns
+                        .iter()
+                        .map(|c| cell_text(&column_value(r, c)))
+                        .collect()
+                })
+                .collect();
+            let mut widths: Vec<usize> = columns
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    cells
+                        .iter()
+                        .map(|row| row[i].chars().count())
+                        .chain([c.len()])
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+            if let Some(max) = output_width() {
+                let floors: Vec<usize> = columns.iter().map(|c| c.len().max(3)).collect();
+                fit_widths(&mut widths, &floors, max);
+            }
+            let line = |values: Vec<String>| {
+                let padded: Vec<String> = values
+                    .iter()
+                    .zip(&widths)
+                    .map(|(v, &w)| format!("{:<w$}", truncate_cell(v, w)))
+                    .collect();
+                format!(" {}", padded.join(" ").trim_end())
+            };
+            println!(
+                "{}",
+                line(columns.iter().map(|c| c.to_uppercase()).collect())
+            );
+            println!(
+                "{}",
+                "-".repeat(widths.iter().sum::<usize>() + widths.len())
+            );
+            for row in cells {
+                println!("{}", line(row));
+            }
+            println!();
+            println!("{} result(s) shown", rows.len());
+        }
+    }
+    Ok(())
+}
+
+/// Width to fit text tables to: `$COLUMNS` if set, otherwise the terminal's
+/// width when stdout is one. `None` when piped, so nothing gets cut.
+fn output_width() -> Option<usize> {
+    if let Some(n) = std::env::var("COLUMNS").ok().and_then(|v| v.parse().ok()) {
+        return Some(n);
+    }
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    terminal_size::terminal_size().map(|(w, _)| w.0 as usize)
+}
+
+/// Shrink the widest columns, never below `floors`, until a row (a leading
+/// space plus one space per column) fits in `max`.
+fn fit_widths(widths: &mut [usize], floors: &[usize], max: usize) {
+    loop {
+        let total = widths.iter().sum::<usize>() + widths.len();
+        if total <= max {
+            return;
+        }
+        let widest = widths
+            .iter()
+            .zip(floors)
+            .enumerate()
+            .filter(|(_, (w, f))| w > f)
+            .max_by_key(|(_, (w, _))| **w)
+            .map(|(i, _)| i);
+        let Some(i) = widest else {
+            return;
+        };
+        widths[i] -= 1;
+    }
+}
+
+/// `text` cut to `width` characters, ending in `…` when anything was cut.
+fn truncate_cell(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+/// A JSON value as a table cell: `-` for null, lists comma-separated.
+fn cell_text(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::Null => "-".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) if items.is_empty() => "-".to_string(),
+        serde_json::Value::Array(items) => {
+            items.iter().map(cell_text).collect::<Vec<_>>().join(",")
+        }
+        other => other.to_string(),
+    }
+}
+
+// ─── csv / ndjson / markdown ─────────────────────────────────────────────────
+
+impl TableFormat {
+    /// Markdown is read by people, so it gets the summary columns; csv and
+    /// ndjson are for tools and get every field.
+    fn columns<'a>(self, all: &'a [&'a str], summary: &'a [&'a str]) -> &'a [&'a str] {
+        match self {
+            TableFormat::Csv | TableFormat::Ndjson => all,
+            TableFormat::Markdown => summary,
+        }
+    }
+}
+
+fn field_names(fields: &[query::Field]) -> Vec<&'static str> {
+    fields.iter().map(|f| f.name).collect()
+}
+
+/// Field `name` of a serialized row. `build` is the "2.3.1 (412)" label
+/// that text output shows, from `app_version` and `build_number`.
+fn column_value(row: &serde_json::Value, name: &str) -> serde_json::Value {
+    if name == "build" && row.get("build").is_none() {
+        return db::build_label(row["app_version"].as_str(), row["build_number"].as_str())
+            .map_or(serde_json::Value::Null, serde_json::Value::String);
+    }
+    row[name].clone()
+}
+
+/// A row cut down to `columns`, serialized with its keys in that order.
+struct Projection<'a> {
+    columns: &'a [String],
+    row: &'a serde_json::Value,
+}
+
+impl serde::Serialize for Projection<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for c in self.columns {
+            map.serialize_entry(c, &column_value(self.row, c))?;
+        }
+        map.end()
+    }
+}
+
+/// Print `rows` as csv, ndjson or a Markdown table of `columns`, writing
+/// each row as soon as it's formatted.
+fn print_table<T: serde::Serialize>(
+    format: TableFormat,
+    columns: &[&str],
+    rows: &[T],
+) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        TableFormat::Csv => {
+            let header: Vec<String> = columns.iter().map(|c| csv_field(c)).collect();
+            writeln!(stdout, "{}", header.join(","))?;
+        }
+        TableFormat::Markdown => {
+            writeln!(stdout, "| {} |", columns.join(" | "))?;
+            writeln!(stdout, "|{}", "---|".repeat(columns.len()))?;
+        }
+        TableFormat::Ndjson => {}
+    }
+    for row in rows {
+        let row = serde_json::to_value(row)?;
+        let values = columns.iter().map(|c| column_value(&row, c));
+        match format {
+            TableFormat::Csv => {
+                let cells: Vec<String> = values
+                    .map(|v| match v {
+                        serde_json::Value::Null => String::new(),
+                        serde_json::Value::Array(ref items) if items.is_empty() => String::new(),
+                        v => csv_field(&cell_text(&v)),
+                    })
+                    .collect();
+                writeln!(stdout, "{}", cells.join(","))?;
+            }
+            TableFormat::Ndjson => {
+                // Built by hand to keep the columns in order.
+                let fields: Vec<String> = columns
+                    .iter()
+                    .zip(values)
+                    .map(|(c, v)| Ok(format!("{}:{v}", serde_json::to_string(c)?)))
+                    .collect::<Result<_>>()?;
+                writeln!(stdout, "{{{}}}", fields.join(","))?;
+            }
+            TableFormat::Markdown => {
+                let cells: Vec<String> = values
+                    .map(|v| {
+                        let text = cell_text(&v).replace('|', "\\|");
+                        text.split_whitespace().collect::<Vec<_>>().join(" ")
+                    })
+                    .collect();
+                writeln!(stdout, "| {} |", cells.join(" | "))?;
+            }
         }
     }
     Ok(())
 }
 
+/// Quote a CSV field if it needs it (RFC 4180).
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
 fn make_client(cfg: &config::Config) -> Result<client::AscClient> {
     client::AscClient::new(
+        cfg.api
+            .base_url
+            .as_deref()
+            .unwrap_or(client::DEFAULT_BASE_URL),
         cfg.api.issuer_id.clone(),
         cfg.api.key_id.clone(),
         cfg.api.private_key.clone(),
+        cfg.api.token_ttl_secs.unwrap_or(auth::MAX_TOKEN_TTL_SECS),
+        cfg.retry.clone(),
     )
 }
 
+/// A single-line `label done/total` counter on stderr, shown only in text
+/// mode on a terminal. Cleared when finished.
+struct Progress {
+    label: &'static str,
+    done: usize,
+    total: usize,
+    enabled: bool,
+}
+
+impl Progress {
+    fn new(label: &'static str, total: usize, fmt: &Format) -> Self {
+        Self {
+            label,
+            done: 0,
+            total,
+            enabled: total > 0 && matches!(fmt, Format::Text) && std::io::stderr().is_terminal(),
+        }
+    }
+
+    fn tick(&mut self) {
+        self.done += 1;
+        if self.enabled {
+            eprint!(
+                "\r  downloading {} {}/{}",
+                self.label, self.done, self.total
+            );
+            let _ = std::io::stderr().flush();
+        }
+    }
+
+    fn finish(&self) {
+        if self.enabled {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+/// Leading column for list rows: `*` when the submission is gone from App
+/// Store Connect.
+fn expired_marker(remote_deleted_at: Option<&str>) -> &'static str {
+    if remote_deleted_at.is_some() {
+        "*"
+    } else {
+        " "
+    }
+}
+
+/// Load the dSYM index from `--dsym-dir` or `[symbolication] dsym_dir`.
+fn open_symbolicator(
+    cfg: &config::Config,
+    explicit: Option<&Path>,
+) -> Result<symbolicate::Symbolicator> {
+    let dir = explicit.or(cfg.symbolication.dsym_dir.as_deref()).context(
+        "no dSYM directory configured. Pass --dsym-dir or add to config.toml:\n\n\
+             [symbolication]\n\
+             dsym_dir = \"path/to/dsyms\"",
+    )?;
+    symbolicate::Symbolicator::scan(dir)
+}
+
+/// Symbolicate a crash log and write the result next to it as
+/// `{id}.symbolicated.ips`.
+fn symbolicate_log(
+    db: &CrashDb,
+    symbolicator: &mut symbolicate::Symbolicator,
+    crash: &CrashRow,
+    log_path: &Path,
+    text: &str,
+) -> Result<symbolicate::Symbolicated> {
+    let out = symbolicate::symbolicate_ips(text, symbolicator)?;
+    let path = log_path.with_file_name(format!("{}.symbolicated.ips", crash.id));
+    std::fs::write(&path, &out.text)?;
+    db.set_symbolicated_log(crash.id, &path.to_string_lossy())?;
+    Ok(out)
+}
+
+/// The log to parse: the symbolicated copy when there is one.
+fn report_log_path(crash: &CrashRow) -> Option<&str> {
+    crash
+        .symbolicated_log_path
+        .as_deref()
+        .or(crash.log_path.as_deref())
+}
+
+/// Index a downloaded crash log for `search`, parse it, store the result and
+/// assign the crash to its signature group. Parse failures are recorded (and
+/// logged) rather than returned, so one odd log can't abort a sync.
+fn parse_and_store(db: &CrashDb, crash: &CrashRow, text: &str) -> Result<Option<ips::CrashReport>> {
+    db.index_log(crash.id, text)?;
+    match ips::parse(text) {
+        Ok(report) => {
+            db.save_report(crash.id, &report)?;
+            assign_group(db, crash, &report)?;
+            Ok(Some(report))
+        }
+        Err(e) => {
+            warn!(id = crash.id, err = %e, "failed to parse crash log");
+            db.save_report_error(crash.id, &format!("{e:#}"))?;
+            db.set_crash_group(crash.id, None)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Compute a crash's signature and link it to the matching group.
+fn assign_group(
+    db: &CrashDb,
+    crash: &CrashRow,
+    report: &ips::CrashReport,
+) -> Result<Option<signature::Signature>> {
+    let sig = signature::compute(report);
+    let group_id = match sig {
+        Some(ref sig) => Some(db.upsert_group(crash.app_id, sig)?),
+        None => None,
+    };
+    if let (Some(old), Some(new)) = (crash.group_id, group_id) {
+        if old != new {
+            db.carry_group_state(old, new)?;
+        }
+    }
+    db.set_crash_group(crash.id, group_id)?;
+    Ok(sig)
+}
+
+fn print_report_summary(r: &ips::CrashReport) {
+    if let Some(ref v) = r.exception_type {
+        match r.exception_subtype {
+            Some(ref sub) => println!("Exception:  {v} ({sub})"),
+            None => println!("Exception:  {v}"),
+        }
+    }
+    if let Some(ref v) = r.signal {
+        println!("Signal:     {v}");
+    }
+    if let Some(ref v) = r.termination_reason {
+        println!("Terminated: {v}");
+    }
+    if let Some(t) = r.crashed() {
+        println!();
+        match t.name.as_deref().or(t.queue.as_deref()) {
+            Some(name) => println!("--- Crashed thread {} ({name}) ---", t.index),
+            None => println!("--- Crashed thread {} ---", t.index),
+        }
+        for f in t.frames.iter().take(20) {
+            let mut symbol = match (&f.symbol, f.symbol_offset) {
+                (Some(sym), Some(off)) => format!("{sym} + {off}"),
+                (Some(sym), None) => sym.clone(),
+                (None, _) => format!("0x{:x}", f.image_offset.unwrap_or(0)),
+            };
+            if let Some(ref file) = f.source_file {
+                let file = file.rsplit('/').next().unwrap_or(file);
+                match f.source_line {
+                    Some(line) => symbol.push_str(&format!(" ({file}:{line})")),
+                    None => symbol.push_str(&format!(" ({file})")),
+                }
+            }
+            println!(
+                "{:<3} {:<30} {symbol}",
+                f.index,
+                f.image_name.as_deref().unwrap_or("???"),
+            );
+        }
+        if t.frames.len() > 20 {
+            println!("... ({} more frames)", t.frames.len() - 20);
+        }
+    }
+}
+
+/// Status timeline for `show` / `feedback show`, oldest first.
+fn print_history(events: &[db::StatusEvent]) {
+    if events.is_empty() {
+        return;
+    }
+    println!("History:");
+    for e in events {
+        let mut line = format!(
+            "  {}  {} → {}  ({})",
+            e.created_at
+                .get(..19)
+                .unwrap_or(&e.created_at)
+                .replace('T', " "),
+            e.old_status.as_deref().unwrap_or("?"),
+            e.new_status,
+            e.actor
+        );
+        if let Some(ref n) = e.notes {
+            line.push_str(&format!(" — {n}"));
+        }
+        println!("{line}");
+    }
+}
+
+/// Notes for `show` / `feedback show`, oldest first.
+fn print_comments(comments: &[db::Comment]) {
+    if comments.is_empty() {
+        return;
+    }
+    println!("Notes:");
+    for c in comments {
+        println!(
+            "  {}  ({})",
+            c.created_at
+                .get(..19)
+                .unwrap_or(&c.created_at)
+                .replace('T', " "),
+            c.actor
+        );
+        for line in c.body.lines() {
+            println!("    {line}");
+        }
+    }
+}
+
 fn crash_to_json(c: &CrashRow) -> serde_json::Value {
     serde_json::json!({
         "id": c.id,
@@ -1225,8 +4163,19 @@ fn crash_to_json(c: &CrashRow) -> serde_json::Value {
         "tester_email": c.tester_email,
         "tester_comment": c.tester_comment,
         "bundle_id": c.bundle_id,
+        "build_id": c.build_id,
+        "app_version": c.app_version,
+        "build_number": c.build_number,
         "has_log": c.has_log,
         "log_path": c.log_path,
+        "symbolicated_log_path": c.symbolicated_log_path,
+        "exception_type": c.exception_type,
+        "signal": c.signal,
+        "group_id": c.group_id,
+        "group_hash": c.group_hash,
+        "signature": c.signature,
+        "regression_of": c.regression_of,
+        "duplicate_of": c.duplicate_of,
         "status": c.status,
         "app_bundle_id": c.app_bundle_id,
         "app_name": c.app_name,
@@ -1244,6 +4193,9 @@ fn feedback_to_json(f: &FeedbackRow) -> serde_json::Value {
         "tester_email": f.tester_email,
         "tester_comment": f.tester_comment,
         "bundle_id": f.bundle_id,
+        "build_id": f.build_id,
+        "app_version": f.app_version,
+        "build_number": f.build_number,
         "has_screenshot": f.has_screenshot,
         "screenshot_path": f.screenshot_path,
         "screenshot_mime_type": f.screenshot_mime_type,
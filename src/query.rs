@@ -0,0 +1,633 @@
+//! Filter expressions for `list --where` and `feedback list --where`,
+//! compiled to parameterized SQL.
+//!
+//! ```text
+//! expr       := and ("or" and)*
+//! and        := unary ("and" unary)*
+//! unary      := "not" unary | "(" expr ")" | comparison
+//! comparison := FIELD op value
+//!             | FIELD "is" ["not"] "null"
+//!             | FIELD ["not"] "in" "(" value ("," value)* ")"
+//! op         := "=" | "!=" | "<" | "<=" | ">" | ">=" | "~" | "!~"
+//! value      := "string" | 'string' | number | true | false
+//! ```
+//!
+//! Fields are the JSON names of `CrashRow` / `FeedbackRow`. `~` is SQL
+//! `LIKE` (`%` and `_` wildcards, case-insensitive). Text compares as text,
+//! except version fields, whose dot-separated numbers compare numerically:
+//! `os_version >= "17"` matches "17.0" and "18.1" but not "9.3". A row with
+//! no value for a field matches `!=`, `!~`, `not in` and `not (…)` on it, and
+//! no other comparison. Keywords are case-insensitive. Values are always
+//! bound, never spliced into the SQL.
+
+use anyhow::{bail, Result};
+use rusqlite::types::ToSql;
+use std::cmp::Ordering;
+
+/// SQLite collation for version fields; `CrashDb` registers it on every
+/// connection.
+pub const VERSION_COLLATION: &str = "version";
+
+/// How a field's values are typed and compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    /// Text like "17.4" or "2.10.1", compared and sorted by
+    /// `VERSION_COLLATION`.
+    Version,
+    Int,
+    Bool,
+    /// Labels from the `tags` table; `sql` is the row id. `tags = "p0"`
+    /// means "tagged p0".
+    Tags(&'static str),
+}
+
+/// A field usable in `--where` and `--sort`.
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub sql: &'static str,
+    pub kind: Kind,
+}
+
+impl Field {
+    pub const fn text(name: &'static str, sql: &'static str) -> Self {
+        Self {
+            name,
+            sql,
+            kind: Kind::Text,
+        }
+    }
+
+    pub const fn version(name: &'static str, sql: &'static str) -> Self {
+        Self {
+            name,
+            sql,
+            kind: Kind::Version,
+        }
+    }
+
+    pub const fn int(name: &'static str, sql: &'static str) -> Self {
+        Self {
+            name,
+            sql,
+            kind: Kind::Int,
+        }
+    }
+
+    pub const fn bool(name: &'static str, sql: &'static str) -> Self {
+        Self {
+            name,
+            sql,
+            kind: Kind::Bool,
+        }
+    }
+
+    /// Tags of `entity` rows, keyed by the row id in `sql`.
+    pub const fn tags(name: &'static str, entity: &'static str, sql: &'static str) -> Self {
+        Self {
+            name,
+            sql,
+            kind: Kind::Tags(entity),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Like,
+    NotLike,
+}
+
+impl Op {
+    fn sql(self) -> &'static str {
+        match self {
+            Op::Eq => "=",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Like => "LIKE",
+            Op::NotLike => "NOT LIKE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    /// Kept as written; converted once the field's kind is known.
+    Number(String),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cmp {
+        field: String,
+        op: Op,
+        value: Value,
+    },
+    IsNull {
+        field: String,
+        negated: bool,
+    },
+    In {
+        field: String,
+        values: Vec<Value>,
+        negated: bool,
+    },
+}
+
+/// One `--sort` key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+    pub field: String,
+    pub desc: bool,
+}
+
+// ─── Lexer ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Op(Op),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => tokens.push((start, Token::LParen)),
+            ')' => tokens.push((start, Token::RParen)),
+            ',' => tokens.push((start, Token::Comma)),
+            '"' | '\'' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => bail!("unterminated string starting at position {}", start + 1),
+                        Some('\\') if chars.get(i + 1).is_some() => {
+                            s.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&q) if q == c => break,
+                        Some(&ch) => {
+                            s.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push((start, Token::Str(s)));
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                let next = chars.get(i + 1).copied();
+                let (op, len) = match (c, next) {
+                    ('=', Some('=')) => (Op::Eq, 2),
+                    ('=', _) => (Op::Eq, 1),
+                    ('!', Some('=')) => (Op::Ne, 2),
+                    ('!', Some('~')) => (Op::NotLike, 2),
+                    ('<', Some('>')) => (Op::Ne, 2),
+                    ('<', Some('=')) => (Op::Le, 2),
+                    ('<', _) => (Op::Lt, 1),
+                    ('>', Some('=')) => (Op::Ge, 2),
+                    ('>', _) => (Op::Gt, 1),
+                    ('~', _) => (Op::Like, 1),
+                    _ => bail!("unexpected '{c}' at position {}", start + 1),
+                };
+                tokens.push((start, Token::Op(op)));
+                i += len;
+                continue;
+            }
+            _ if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) =>
+            {
+                i += 1;
+                while chars
+                    .get(i)
+                    .is_some_and(|d| d.is_ascii_digit() || *d == '.')
+                {
+                    i += 1;
+                }
+                tokens.push((start, Token::Number(chars[start..i].iter().collect())));
+                continue;
+            }
+            _ if c.is_ascii_alphabetic() || c == '_' => {
+                while chars
+                    .get(i)
+                    .is_some_and(|d| d.is_ascii_alphanumeric() || *d == '_')
+                {
+                    i += 1;
+                }
+                tokens.push((start, Token::Ident(chars[start..i].iter().collect())));
+                continue;
+            }
+            _ => bail!("unexpected '{c}' at position {}", start + 1),
+        }
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+// ─── Parser ──────────────────────────────────────────────────────────────────
+
+/// Parse a `--where` expression.
+pub fn parse(input: &str) -> Result<Expr> {
+    let mut p = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    if p.tokens.is_empty() {
+        bail!("empty filter expression");
+    }
+    let expr = p.or()?;
+    if let Some((at, tok)) = p.tokens.get(p.pos) {
+        bail!("unexpected {} at position {}", describe(tok), at + 1);
+    }
+    Ok(expr)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    /// Fail with the position of the current token.
+    fn unexpected<T>(&self, expected: &str) -> Result<T> {
+        match self.tokens.get(self.pos) {
+            Some((at, t)) => bail!(
+                "expected {expected}, found {} at position {}",
+                describe(t),
+                at + 1
+            ),
+            None => bail!("expected {expected} at end of expression"),
+        }
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(w)) if w.eq_ignore_ascii_case(word) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut left = self.and()?;
+        while self.keyword("or") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut left = self.unary()?;
+        while self.keyword("and") {
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.keyword("not") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let expr = self.or()?;
+            if self.peek() != Some(&Token::RParen) {
+                return self.unexpected("')'");
+            }
+            self.pos += 1;
+            return Ok(expr);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let field = match self.peek() {
+            Some(Token::Ident(f)) if !is_keyword(f) => f.clone(),
+            _ => return self.unexpected("a field name"),
+        };
+        self.pos += 1;
+
+        if self.keyword("is") {
+            let negated = self.keyword("not");
+            if !self.keyword("null") {
+                return self.unexpected("'null'");
+            }
+            return Ok(Expr::IsNull { field, negated });
+        }
+        let negated = self.keyword("not");
+        if self.keyword("in") {
+            if self.peek() != Some(&Token::LParen) {
+                return self.unexpected("'('");
+            }
+            self.pos += 1;
+            let mut values = vec![self.value()?];
+            while self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+                values.push(self.value()?);
+            }
+            if self.peek() != Some(&Token::RParen) {
+                return self.unexpected("',' or ')'");
+            }
+            self.pos += 1;
+            return Ok(Expr::In {
+                field,
+                values,
+                negated,
+            });
+        }
+        if negated {
+            return self.unexpected("'in'");
+        }
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return self.unexpected("an operator"),
+        };
+        self.pos += 1;
+        let value = self.value()?;
+        Ok(Expr::Cmp { field, op, value })
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let value = match self.peek() {
+            Some(Token::Str(s)) => Value::Text(s.clone()),
+            Some(Token::Number(n)) => Value::Number(n.clone()),
+            Some(Token::Ident(w)) if w.eq_ignore_ascii_case("true") => Value::Bool(true),
+            Some(Token::Ident(w)) if w.eq_ignore_ascii_case("false") => Value::Bool(false),
+            _ => return self.unexpected("a value"),
+        };
+        self.pos += 1;
+        Ok(value)
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    ["and", "or", "not", "is", "null", "in", "true", "false"]
+        .iter()
+        .any(|k| word.eq_ignore_ascii_case(k))
+}
+
+fn describe(tok: &Token) -> String {
+    match tok {
+        Token::Ident(w) => format!("'{w}'"),
+        Token::Str(s) => format!("\"{s}\""),
+        Token::Number(n) => n.clone(),
+        Token::Op(op) => format!("'{}'", op.sql()),
+        Token::LParen => "'('".into(),
+        Token::RParen => "')'".into(),
+        Token::Comma => "','".into(),
+    }
+}
+
+/// Parse `--sort field[:asc|:desc][,field…]`.
+pub fn parse_sort(arg: &str) -> Result<Vec<SortKey>> {
+    arg.split(',')
+        .map(|key| {
+            let key = key.trim();
+            let (field, desc) = match key.split_once(':') {
+                Some((f, "desc")) => (f, true),
+                Some((f, "asc")) => (f, false),
+                Some((_, dir)) => bail!("invalid sort direction '{dir}'; use asc or desc"),
+                None => (key, false),
+            };
+            if field.is_empty() {
+                bail!("empty sort field in '{arg}'");
+            }
+            Ok(SortKey {
+                field: field.to_string(),
+                desc,
+            })
+        })
+        .collect()
+}
+
+// ─── SQL generation ──────────────────────────────────────────────────────────
+
+fn field<'a>(fields: &'a [Field], name: &str) -> Result<&'a Field> {
+    fields.iter().find(|f| f.name == name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "unknown field '{name}'; expected one of: {}",
+            fields.iter().map(|f| f.name).collect::<Vec<_>>().join(", ")
+        )
+    })
+}
+
+/// Convert a literal to what `f` stores.
+fn bind(f: &Field, value: &Value) -> Result<Box<dyn ToSql>> {
+    Ok(match (f.kind, value) {
+        (Kind::Int, Value::Number(n)) => match n.parse::<i64>() {
+            Ok(i) => Box::new(i),
+            Err(_) => bail!("'{}' expects a whole number, got {n}", f.name),
+        },
+        (Kind::Int, Value::Text(s)) => match s.parse::<i64>() {
+            Ok(i) => Box::new(i),
+            Err(_) => bail!("'{}' expects a whole number, got \"{s}\"", f.name),
+        },
+        (Kind::Bool, Value::Bool(b)) => Box::new(*b),
+        (Kind::Bool, Value::Number(n)) if n == "0" || n == "1" => Box::new(n == "1"),
+        (Kind::Bool, _) => bail!("'{}' expects true or false", f.name),
+        (_, Value::Text(s) | Value::Number(s)) => Box::new(s.clone()),
+        (_, Value::Bool(b)) => bail!("'{}' is not a true/false field, got {b}", f.name),
+    })
+}
+
+fn placeholder(
+    value: Box<dyn ToSql>,
+    idx: &mut usize,
+    bind_values: &mut Vec<Box<dyn ToSql>>,
+) -> String {
+    bind_values.push(value);
+    *idx += 1;
+    format!("?{}", *idx - 1)
+}
+
+/// Compile `expr` to a SQL condition over `fields`, numbering placeholders
+/// from `idx` and pushing their values onto `bind_values`.
+pub fn to_sql(
+    expr: &Expr,
+    fields: &[Field],
+    idx: &mut usize,
+    bind_values: &mut Vec<Box<dyn ToSql>>,
+) -> Result<String> {
+    Ok(match expr {
+        Expr::And(a, b) => format!(
+            "({} AND {})",
+            to_sql(a, fields, idx, bind_values)?,
+            to_sql(b, fields, idx, bind_values)?
+        ),
+        Expr::Or(a, b) => format!(
+            "({} OR {})",
+            to_sql(a, fields, idx, bind_values)?,
+            to_sql(b, fields, idx, bind_values)?
+        ),
+        // A comparison against NULL is NULL, and NOT NULL is still NULL; count
+        // it as false first so `not (x ~ "a")` keeps rows without x, as
+        // `x !~ "a"` does.
+        Expr::Not(e) => format!(
+            "(NOT COALESCE({}, 0))",
+            to_sql(e, fields, idx, bind_values)?
+        ),
+        Expr::IsNull {
+            field: name,
+            negated,
+        } => {
+            let f = field(fields, name)?;
+            match f.kind {
+                Kind::Tags(entity) => format!(
+                    "({}EXISTS (SELECT 1 FROM tags WHERE entity = '{entity}' AND entity_id = {}))",
+                    if *negated { "" } else { "NOT " },
+                    f.sql
+                ),
+                _ => format!("({} IS {}NULL)", f.sql, if *negated { "NOT " } else { "" }),
+            }
+        }
+        Expr::Cmp {
+            field: name,
+            op,
+            value,
+        } => {
+            let f = field(fields, name)?;
+            if matches!(op, Op::Like | Op::NotLike) && matches!(f.kind, Kind::Int | Kind::Bool) {
+                bail!("'~' only applies to text fields, not '{}'", f.name);
+            }
+            let p = placeholder(bind(f, value)?, idx, bind_values);
+            let sql = match f.kind {
+                Kind::Version if !matches!(op, Op::Like | Op::NotLike) => {
+                    format!("{} COLLATE {VERSION_COLLATION}", f.sql)
+                }
+                _ => f.sql.to_string(),
+            };
+            match f.kind {
+                Kind::Tags(entity) => {
+                    let (negated, op) = match op {
+                        Op::Eq => (false, "="),
+                        Op::Ne => (true, "="),
+                        Op::Like => (false, "LIKE"),
+                        Op::NotLike => (true, "LIKE"),
+                        _ => bail!("'{}' supports only =, !=, ~ and !~", f.name),
+                    };
+                    format!(
+                        "({}EXISTS (SELECT 1 FROM tags WHERE entity = '{entity}' \
+                         AND entity_id = {} AND tag {op} {p}))",
+                        if negated { "NOT " } else { "" },
+                        f.sql
+                    )
+                }
+                // NULL never compares unequal; `status != "x"` should still
+                // match rows with no value.
+                _ if matches!(op, Op::Ne | Op::NotLike) => {
+                    format!("({} IS NULL OR {sql} {} {p})", f.sql, op.sql())
+                }
+                _ => format!("({sql} {} {p})", op.sql()),
+            }
+        }
+        Expr::In {
+            field: name,
+            values,
+            negated,
+        } => {
+            let f = field(fields, name)?;
+            let list = values
+                .iter()
+                .map(|v| Ok(placeholder(bind(f, v)?, idx, bind_values)))
+                .collect::<Result<Vec<_>>>()?
+                .join(", ");
+            let sql = match f.kind {
+                Kind::Version => format!("{} COLLATE {VERSION_COLLATION}", f.sql),
+                _ => f.sql.to_string(),
+            };
+            match (f.kind, negated) {
+                (Kind::Tags(entity), _) => format!(
+                    "({}EXISTS (SELECT 1 FROM tags WHERE entity = '{entity}' \
+                     AND entity_id = {} AND tag IN ({list})))",
+                    if *negated { "NOT " } else { "" },
+                    f.sql
+                ),
+                (_, false) => format!("({sql} IN ({list}))"),
+                (_, true) => format!("({} IS NULL OR {sql} NOT IN ({list}))", f.sql),
+            }
+        }
+    })
+}
+
+/// Compile sort keys to an `ORDER BY` list (without the keywords).
+pub fn order_by(keys: &[SortKey], fields: &[Field]) -> Result<String> {
+    keys.iter()
+        .map(|k| {
+            let f = field(fields, &k.field)?;
+            let collate = match f.kind {
+                Kind::Tags(_) => bail!("can't sort by '{}'", f.name),
+                Kind::Version => format!(" COLLATE {VERSION_COLLATION}"),
+                _ => String::new(),
+            };
+            Ok(format!(
+                "{}{collate}{}",
+                f.sql,
+                if k.desc { " DESC" } else { "" }
+            ))
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|v| v.join(", "))
+}
+
+/// Order version strings by their numeric parts: "9.3" < "17" < "17.0" <
+/// "17.9" < "17.10". Anything before the first digit (an "iOS " prefix) is
+/// skipped; strings whose numbers tie fall back to plain text order.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (ka, kb) = (version_parts(a), version_parts(b));
+    ka.iter()
+        .zip(&kb)
+        .map(|(x, y)| x.len().cmp(&y.len()).then_with(|| x.cmp(y)))
+        .find(|o| o.is_ne())
+        .unwrap_or_else(|| ka.len().cmp(&kb.len()))
+        .then_with(|| a.cmp(b))
+}
+
+/// The dot-separated digit runs of `s`, without leading zeros.
+fn version_parts(s: &str) -> Vec<&str> {
+    let Some(start) = s.find(|c: char| c.is_ascii_digit()) else {
+        return Vec::new();
+    };
+    let rest = &s[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    rest[..end]
+        .split('.')
+        .take_while(|part| !part.is_empty())
+        .map(|part| part.trim_start_matches('0'))
+        .collect()
+}
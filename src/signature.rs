@@ -0,0 +1,121 @@
+//! Crash signatures for automatic grouping.
+//!
+//! A signature is the exception type plus the top in-app frames of the
+//! crashed thread, normalized so the same bug reported from different
+//! devices collapses into one group. Frames outside the app bundle
+//! (UIKit, libsystem, …) are skipped unless the crashed thread has no
+//! in-app frames at all.
+
+use crate::ips::{BinaryImage, CrashReport, Frame};
+
+/// Number of crashed-thread frames that make up a signature.
+pub const SIGNATURE_FRAMES: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Signature {
+    /// Short stable identifier derived from `text`.
+    pub hash: String,
+    pub exception_type: Option<String>,
+    pub frames: Vec<String>,
+    /// Full normalized signature; the grouping key.
+    pub text: String,
+}
+
+/// Compute the signature of a parsed report. Returns None if the report has
+/// neither an exception type nor a crashed thread to work from.
+pub fn compute(report: &CrashReport) -> Option<Signature> {
+    let crashed = report.crashed();
+    if report.exception_type.is_none() && crashed.is_none() {
+        return None;
+    }
+
+    let frames: Vec<String> = match crashed {
+        Some(t) => {
+            let in_app: Vec<&Frame> = t
+                .frames
+                .iter()
+                .filter(|f| is_in_app(f, &report.images))
+                .collect();
+            let chosen: Vec<&Frame> = if in_app.is_empty() {
+                t.frames.iter().collect()
+            } else {
+                in_app
+            };
+            chosen
+                .into_iter()
+                .take(SIGNATURE_FRAMES)
+                .map(normalize_frame)
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    let exception = report.exception_type.as_deref().unwrap_or("UNKNOWN");
+    let mut text = exception.to_string();
+    for f in &frames {
+        text.push_str(" | ");
+        text.push_str(f);
+    }
+
+    Some(Signature {
+        hash: format!("{:016x}", fnv1a(text.as_bytes())),
+        exception_type: report.exception_type.clone(),
+        frames,
+        text,
+    })
+}
+
+/// A frame is in-app when its image lives inside the `.app` bundle
+/// (the main executable or an embedded framework).
+fn is_in_app(frame: &Frame, images: &[BinaryImage]) -> bool {
+    frame
+        .image_index
+        .and_then(|i| images.get(i as usize))
+        .and_then(|img| img.path.as_deref())
+        .map(|p| p.contains(".app/") && !p.starts_with("/System/"))
+        .unwrap_or(false)
+}
+
+fn normalize_frame(frame: &Frame) -> String {
+    let image = frame.image_name.as_deref().unwrap_or("???");
+    match frame.symbol {
+        Some(ref sym) => format!("{image}: {}", normalize_symbol(sym)),
+        None => format!("{image}+0x{:x}", frame.image_offset.unwrap_or(0)),
+    }
+}
+
+/// Strip the parts of a symbol that vary between otherwise identical crashes:
+/// Swift specialization markers, embedded addresses and whitespace.
+fn normalize_symbol(sym: &str) -> String {
+    let sym = sym.trim();
+    let sym = sym.strip_prefix("specialized ").unwrap_or(sym);
+
+    let mut out = String::with_capacity(sym.len());
+    let mut chars = sym.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '0' && chars.peek() == Some(&'x') {
+            chars.next();
+            while chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                chars.next();
+            }
+            out.push_str("0x?");
+        } else if c.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// 64-bit FNV-1a — stable across Rust versions, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
@@ -0,0 +1,413 @@
+//! Offline symbolication of crash frames against local dSYM bundles.
+//!
+//! Binary image UUIDs from the `.ips` report are matched against the Mach-O
+//! files inside `*.dSYM/Contents/Resources/DWARF/`, and frame addresses are
+//! resolved to function/file/line by reading the DWARF directly — no `atos`,
+//! so this works on Linux CI boxes too. Function names come from DWARF when
+//! available (the plain `DW_AT_name`, not the mangled linkage name), falling
+//! back to the Mach-O symbol table.
+
+use anyhow::{Context, Result};
+use gimli::Reader as _;
+use object::read::macho::{FatArch, MachOFatFile32, MachOFatFile64};
+use object::{Object, ObjectSegment, ObjectSymbol, ObjectSymbolTable};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use tracing::{debug, warn};
+
+type Reader = gimli::EndianRcSlice<gimli::RunTimeEndian>;
+
+/// A resolved frame location.
+#[derive(Debug, Clone)]
+pub struct Resolved {
+    pub function: Option<String>,
+    /// Offset of the address from the start of `function`, when known.
+    pub function_offset: Option<u64>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// Result of symbolicating one `.ips` report.
+#[derive(Debug)]
+pub struct Symbolicated {
+    /// The rewritten report, in `.ips` format.
+    pub text: String,
+    /// Frames that gained a symbol or source location.
+    pub resolved: usize,
+    /// Frames still without a symbol afterwards.
+    pub unresolved: usize,
+    /// Images with unsymbolicated frames but no matching dSYM.
+    pub missing_dsyms: Vec<String>,
+}
+
+/// Lazily-loaded DWARF for every dSYM found under a directory, keyed by UUID.
+pub struct Symbolicator {
+    /// Normalized UUID → (DWARF file, byte range of the matching slice).
+    index: HashMap<String, (PathBuf, usize, usize)>,
+    loaded: HashMap<String, Option<LoadedImage>>,
+}
+
+struct LoadedImage {
+    ctx: addr2line::Context<Reader>,
+    /// `__TEXT` vmaddr; image offsets from the report are relative to it.
+    text_vmaddr: u64,
+    /// Sorted (address, name) pairs from the symbol table.
+    symbols: Vec<(u64, String)>,
+}
+
+impl Symbolicator {
+    /// Scan `dir` recursively for dSYM bundles and index them by UUID.
+    pub fn scan(dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            anyhow::bail!("dSYM directory not found: {}", dir.display());
+        }
+        let mut index = HashMap::new();
+        let mut stack = vec![dir.to_path_buf()];
+        while let Some(path) = stack.pop() {
+            let entries = std::fs::read_dir(&path)
+                .with_context(|| format!("could not read {}", path.display()))?;
+            for entry in entries {
+                let p = entry?.path();
+                if p.is_dir() {
+                    stack.push(p);
+                } else if is_dsym_dwarf_file(&p) {
+                    match index_file(&p) {
+                        Ok(slices) => {
+                            for (uuid, start, end) in slices {
+                                debug!(uuid, path = %p.display(), "indexed dSYM");
+                                index.insert(uuid, (p.clone(), start, end));
+                            }
+                        }
+                        Err(e) => warn!(path = %p.display(), err = %e, "skipping dSYM"),
+                    }
+                }
+            }
+        }
+        Ok(Self {
+            index,
+            loaded: HashMap::new(),
+        })
+    }
+
+    pub fn has_uuid(&self, uuid: &str) -> bool {
+        self.index.contains_key(&normalize_uuid(uuid))
+    }
+
+    /// Resolve an offset into the image with the given UUID.
+    pub fn resolve(&mut self, uuid: &str, image_offset: u64) -> Result<Option<Resolved>> {
+        let uuid = normalize_uuid(uuid);
+        if !self.loaded.contains_key(&uuid) {
+            let image = match self.index.get(&uuid) {
+                Some((path, start, end)) => Some(load_image(path, *start, *end)?),
+                None => None,
+            };
+            self.loaded.insert(uuid.clone(), image);
+        }
+        let Some(Some(image)) = self.loaded.get(&uuid) else {
+            return Ok(None);
+        };
+        image.resolve(image_offset)
+    }
+}
+
+impl LoadedImage {
+    fn resolve(&self, image_offset: u64) -> Result<Option<Resolved>> {
+        let addr = self.text_vmaddr + image_offset;
+
+        let mut function = None;
+        let mut linkage_name = None;
+        let mut file = None;
+        let mut line = None;
+
+        let mut frames = self
+            .ctx
+            .find_frames(addr)
+            .skip_all_loads()
+            .context("DWARF lookup failed")?;
+        // The first frame is the innermost (possibly inlined) function.
+        if let Some(frame) = frames.next().context("DWARF lookup failed")? {
+            // addr2line names functions by their mangled linkage name
+            // (`$s5MyApp…`, `_ZN…`); show the plain name, as `atos` would.
+            linkage_name = match frame.function {
+                Some(f) => Some(f.raw_name()?.into_owned()),
+                None => None,
+            };
+            let unit = self.ctx.find_dwarf_and_unit(addr).skip_all_loads();
+            function = match (unit, frame.dw_die_offset) {
+                (Some(unit), Some(offset)) => plain_name(unit, offset)?,
+                _ => None,
+            }
+            .or_else(|| linkage_name.clone());
+            if let Some(loc) = frame.location {
+                file = loc.file.map(str::to_string);
+                line = loc.line;
+            }
+        }
+        if file.is_none() {
+            if let Some(loc) = self.ctx.find_location(addr)? {
+                file = loc.file.map(str::to_string);
+                line = loc.line;
+            }
+        }
+        // The symbol table gives the function start, so it also yields the
+        // offset; use it for the name only when DWARF had none.
+        let nearest = self.symbol_for(addr);
+        let function_offset = match (&linkage_name, nearest) {
+            (None, Some((start, name))) if function.is_none() => {
+                function = Some(name.to_string());
+                Some(addr - start)
+            }
+            (Some(f), Some((start, name))) if f == name => Some(addr - start),
+            _ => None,
+        };
+
+        if function.is_none() && file.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Resolved {
+            function,
+            function_offset,
+            file,
+            line,
+        }))
+    }
+
+    /// Nearest symbol at or before `addr`.
+    fn symbol_for(&self, addr: u64) -> Option<(u64, &str)> {
+        let idx = self.symbols.partition_point(|(a, _)| *a <= addr);
+        idx.checked_sub(1)
+            .map(|i| (self.symbols[i].0, self.symbols[i].1.as_str()))
+    }
+}
+
+/// `DW_AT_name` of the function DIE at `offset`, following the abstract
+/// origin of an inlined copy or the declaration of an out-of-line
+/// definition, which is where the name lives for those.
+fn plain_name(
+    unit: gimli::UnitRef<Reader>,
+    mut offset: gimli::UnitOffset,
+) -> Result<Option<String>> {
+    // Chains are one or two links long; the bound only guards against cycles.
+    for _ in 0..8 {
+        let entry = unit.entry(offset)?;
+        if let Some(name) = entry.attr_value(gimli::DW_AT_name)? {
+            return Ok(Some(
+                unit.attr_string(name)?.to_string_lossy()?.into_owned(),
+            ));
+        }
+        let next = match entry.attr_value(gimli::DW_AT_abstract_origin)? {
+            Some(v) => Some(v),
+            None => entry.attr_value(gimli::DW_AT_specification)?,
+        };
+        match next {
+            Some(gimli::AttributeValue::UnitRef(o)) => offset = o,
+            _ => return Ok(None),
+        }
+    }
+    Ok(None)
+}
+
+/// Symbolicate every frame of an `.ips` report that is missing a symbol or
+/// source location, returning the rewritten report. The header line is kept
+/// verbatim; frames that already carry symbols are left alone.
+pub fn symbolicate_ips(text: &str, sym: &mut Symbolicator) -> Result<Symbolicated> {
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let (header, body) = text
+        .split_once('\n')
+        .context("not an .ips report: missing header line")?;
+    let mut body: serde_json::Value =
+        serde_json::from_str(body).context("invalid .ips body JSON")?;
+
+    let uuids: Vec<Option<String>> = body
+        .get("usedImages")
+        .and_then(|v| v.as_array())
+        .map(|images| {
+            images
+                .iter()
+                .map(|img| img.get("uuid").and_then(|u| u.as_str()).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut resolved = 0;
+    let mut unresolved = 0;
+    let mut missing_dsyms: Vec<String> = Vec::new();
+
+    let mut frame_lists: Vec<&mut Vec<serde_json::Value>> = Vec::new();
+    for (key, value) in body.as_object_mut().into_iter().flatten() {
+        match (key.as_str(), value.as_array_mut()) {
+            ("threads", Some(threads)) => frame_lists.extend(
+                threads
+                    .iter_mut()
+                    .filter_map(|t| t.get_mut("frames").and_then(|f| f.as_array_mut())),
+            ),
+            // Present for uncaught NSExceptions; not part of `threads`.
+            ("lastExceptionBacktrace", Some(frames)) => frame_lists.push(frames),
+            _ => {}
+        }
+    }
+
+    for frame in frame_lists.into_iter().flatten() {
+        let Some(frame) = frame.as_object_mut() else {
+            continue;
+        };
+        let has_symbol = frame.contains_key("symbol");
+        if has_symbol && frame.contains_key("sourceFile") {
+            continue;
+        }
+        let uuid = frame
+            .get("imageIndex")
+            .and_then(|v| v.as_u64())
+            .and_then(|i| uuids.get(i as usize).cloned().flatten());
+        let offset = frame.get("imageOffset").and_then(|v| v.as_u64());
+        let (Some(uuid), Some(offset)) = (uuid, offset) else {
+            unresolved += usize::from(!has_symbol);
+            continue;
+        };
+        if !sym.has_uuid(&uuid) {
+            if !has_symbol {
+                unresolved += 1;
+                if !missing_dsyms.contains(&uuid) {
+                    missing_dsyms.push(uuid);
+                }
+            }
+            continue;
+        }
+
+        let Some(r) = sym.resolve(&uuid, offset)? else {
+            unresolved += usize::from(!has_symbol);
+            continue;
+        };
+        let mut changed = false;
+        if !has_symbol {
+            if let Some(function) = r.function {
+                frame.insert("symbol".into(), function.into());
+                if let Some(off) = r.function_offset {
+                    frame.insert("symbolLocation".into(), off.into());
+                }
+                changed = true;
+            } else {
+                unresolved += 1;
+            }
+        }
+        if let Some(file) = r.file {
+            frame.insert("sourceFile".into(), file.into());
+            if let Some(line) = r.line {
+                frame.insert("sourceLine".into(), line.into());
+            }
+            changed = true;
+        }
+        resolved += usize::from(changed);
+    }
+
+    let body = serde_json::to_string_pretty(&body)?;
+    Ok(Symbolicated {
+        text: format!("{header}\n{body}\n"),
+        resolved,
+        unresolved,
+        missing_dsyms,
+    })
+}
+
+/// dSYM bundles keep their DWARF at `Foo.dSYM/Contents/Resources/DWARF/Foo`.
+fn is_dsym_dwarf_file(path: &Path) -> bool {
+    path.parent()
+        .is_some_and(|p| p.ends_with("Contents/Resources/DWARF"))
+        && path
+            .ancestors()
+            .any(|a| a.extension().is_some_and(|e| e == "dSYM"))
+}
+
+/// Normalize a UUID to lowercase hex without dashes.
+fn normalize_uuid(uuid: &str) -> String {
+    uuid.chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn format_uuid(bytes: [u8; 16]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Return (uuid, start, end) for every Mach-O slice in a file.
+fn index_file(path: &Path) -> Result<Vec<(String, usize, usize)>> {
+    let data = std::fs::read(path)?;
+    let mut out = Vec::new();
+    for (start, end) in macho_slices(&data)? {
+        let file = object::File::parse(&data[start..end])?;
+        if let Some(uuid) = file.mach_uuid()? {
+            out.push((format_uuid(uuid), start, end));
+        }
+    }
+    Ok(out)
+}
+
+/// Byte ranges of the Mach-O images in `data`, unpacking universal binaries.
+fn macho_slices(data: &[u8]) -> Result<Vec<(usize, usize)>> {
+    fn ranges<A: FatArch>(arches: &[A], data: &[u8]) -> Vec<(usize, usize)> {
+        arches
+            .iter()
+            .map(|a| {
+                let (off, size) = a.file_range();
+                (off as usize, (off + size) as usize)
+            })
+            .filter(|(_, end)| *end <= data.len())
+            .collect()
+    }
+    match object::FileKind::parse(data)? {
+        object::FileKind::MachOFat32 => Ok(ranges(MachOFatFile32::parse(data)?.arches(), data)),
+        object::FileKind::MachOFat64 => Ok(ranges(MachOFatFile64::parse(data)?.arches(), data)),
+        object::FileKind::MachO32 | object::FileKind::MachO64 => Ok(vec![(0, data.len())]),
+        other => anyhow::bail!("not a Mach-O file ({other:?})"),
+    }
+}
+
+fn load_image(path: &Path, start: usize, end: usize) -> Result<LoadedImage> {
+    let data = std::fs::read(path).with_context(|| format!("could not read {}", path.display()))?;
+    let file = object::File::parse(&data[start..end])
+        .with_context(|| format!("invalid Mach-O: {}", path.display()))?;
+    let endian = if file.is_little_endian() {
+        gimli::RunTimeEndian::Little
+    } else {
+        gimli::RunTimeEndian::Big
+    };
+
+    let dwarf = gimli::Dwarf::load(|id| -> Result<Reader> {
+        let bytes: Cow<[u8]> = match file.section_by_name(id.name()) {
+            Some(section) => object::ObjectSection::uncompressed_data(&section)?,
+            None => Cow::Borrowed(&[]),
+        };
+        Ok(gimli::EndianRcSlice::new(Rc::from(bytes.as_ref()), endian))
+    })?;
+    let ctx = addr2line::Context::from_dwarf(dwarf).context("invalid DWARF")?;
+
+    let text_vmaddr = file
+        .segments()
+        .find(|s| s.name().ok().flatten() == Some("__TEXT"))
+        .map(|s| s.address())
+        .unwrap_or(0);
+
+    let mut symbols: Vec<(u64, String)> = match file.symbol_table() {
+        Some(table) => table
+            .symbols()
+            .filter(|s| s.is_definition() && s.kind() == object::SymbolKind::Text)
+            .filter_map(|s| {
+                let name = s.name().ok()?;
+                // Mach-O C symbols carry a leading underscore; atos strips it.
+                let name = name.strip_prefix('_').unwrap_or(name);
+                Some((s.address(), name.to_string()))
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    symbols.sort();
+
+    Ok(LoadedImage {
+        ctx,
+        text_vmaddr,
+        symbols,
+    })
+}
@@ -141,3 +141,76 @@ pub struct ScreenshotSubmissionRels {
     pub build: Option<RelData>,
     pub tester: Option<RelData>,
 }
+
+// ─── Build ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct BuildResponse {
+    pub data: Build,
+    /// Only `preReleaseVersions` is requested via `include`.
+    #[serde(default)]
+    pub included: Vec<PreReleaseVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Build {
+    pub id: String,
+    pub attributes: Option<BuildAttrs>,
+    pub relationships: Option<BuildRels>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildAttrs {
+    /// The build number (CFBundleVersion).
+    pub version: Option<String>,
+    pub uploaded_date: Option<DateTime<Utc>>,
+    pub expiration_date: Option<DateTime<Utc>>,
+    pub expired: Option<bool>,
+    pub min_os_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildRels {
+    pub pre_release_version: Option<RelData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreReleaseVersion {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub attributes: Option<PreReleaseVersionAttrs>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreReleaseVersionAttrs {
+    /// The marketing version (CFBundleShortVersionString).
+    pub version: Option<String>,
+    pub platform: Option<String>,
+}
+
+// ─── JSON:API errors ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ErrorResponse {
+    #[serde(default)]
+    pub errors: Vec<ApiError>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiError {
+    pub id: Option<String>,
+    pub status: Option<String>,
+    pub code: Option<String>,
+    pub title: Option<String>,
+    pub detail: Option<String>,
+    pub source: Option<ApiErrorSource>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiErrorSource {
+    pub pointer: Option<String>,
+    pub parameter: Option<String>,
+}
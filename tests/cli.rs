@@ -1,6 +1,9 @@
 //! Integration tests for the asc-crash-fetcher CLI.
 
+mod support;
+
 use std::process::Command;
+use support::{MockAsc, Reply};
 
 fn bin() -> Command {
     // In integration tests, cargo puts the binary in target/debug/ or target/release/
@@ -326,3 +329,1978 @@ fn feedback_screenshot_nonexistent_fails() {
 
     assert!(!output.status.success());
 }
+
+// ─── Parsed crash log tests ───────────────────────────────────────────────────
+
+/// Insert a crash row directly into the database, copying `log_fixture` into
+/// `logs/` as if sync had downloaded it. Returns the crash's local id.
+fn seed_crash(work_dir: &tempfile::TempDir, log_fixture: Option<&str>) -> i64 {
+    let data_dir = work_dir.path().join("asc-crashes");
+    let conn = rusqlite::Connection::open(data_dir.join("crashes.db")).unwrap();
+    conn.execute(
+        "INSERT OR IGNORE INTO apps (bundle_id, asc_id, name) VALUES ('com.test.app', '1', 'Test')",
+        [],
+    )
+    .unwrap();
+    let n: i64 = conn
+        .query_row("SELECT COUNT(*) FROM crashes", [], |r| r.get(0))
+        .unwrap();
+    conn.execute(
+        "INSERT INTO crashes (app_id, submission_id, created_at, device_model, os_version)
+         VALUES (1, ?1, '2025-06-12T16:41:07+00:00', 'iPhone16,2', '18.5')",
+        [format!("sub-{n}")],
+    )
+    .unwrap();
+    let id = conn.last_insert_rowid();
+
+    if let Some(fixture) = log_fixture {
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(fixture);
+        let dst = data_dir.join("logs").join(format!("{id}.ips"));
+        std::fs::copy(src, &dst).unwrap();
+        conn.execute(
+            "UPDATE crashes SET has_log = 1, log_path = ?1 WHERE id = ?2",
+            rusqlite::params![dst.to_string_lossy(), id],
+        )
+        .unwrap();
+    }
+    id
+}
+
+fn run_json(work_dir: &tempfile::TempDir, args: &[&str]) -> serde_json::Value {
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .args(args)
+        .output()
+        .expect("failed to run");
+    assert!(
+        output.status.success(),
+        "{:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("invalid JSON")
+}
+
+#[test]
+fn reparse_extracts_exception_and_frames() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("crash.ips"));
+
+    let out = run_json(&work_dir, &["reparse"]);
+    assert_eq!(out["parsed"], 1);
+
+    let crash = run_json(&work_dir, &["show", &id.to_string()]);
+    assert_eq!(crash["exception_type"], "EXC_BAD_ACCESS");
+    assert_eq!(crash["signal"], "SIGSEGV");
+    assert_eq!(
+        crash["termination_reason"],
+        "SIGNAL 11 Segmentation fault: 11"
+    );
+
+    let report = &crash["report"];
+    assert_eq!(report["crashed_thread"], 0);
+    assert_eq!(report["app_version"], "2.3.1");
+    assert_eq!(report["threads"].as_array().unwrap().len(), 2);
+    let frame = &report["threads"][0]["frames"][0];
+    assert_eq!(frame["image_name"], "MyApp");
+    assert_eq!(frame["symbol"], "SettingsViewController.viewDidLoad()");
+    assert_eq!(frame["image_offset"], 82340);
+    assert_eq!(report["images"][1]["name"], "UIKitCore");
+}
+
+#[test]
+fn list_and_stats_use_parsed_exception() {
+    let work_dir = setup_test_env();
+    seed_crash(&work_dir, Some("crash.ips"));
+    seed_crash(&work_dir, None);
+    run_json(&work_dir, &["reparse"]);
+
+    let list = run_json(&work_dir, &["list", "--exception", "EXC_BAD_ACCESS"]);
+    assert_eq!(list["count"], 1);
+
+    let stats = run_json(&work_dir, &["stats"]);
+    assert_eq!(stats["by_exception"][0][0], "EXC_BAD_ACCESS");
+    assert_eq!(stats["by_exception"][0][1], 1);
+}
+
+#[test]
+fn reparse_records_unparseable_logs() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("crash.ips"));
+    let log = work_dir.path().join(format!("asc-crashes/logs/{id}.ips"));
+    std::fs::write(&log, "Incident Identifier: legacy text crash\n").unwrap();
+
+    let out = run_json(&work_dir, &["reparse"]);
+    assert_eq!(out["parsed"], 0);
+    assert_eq!(out["failed"][0], id);
+
+    let crash = run_json(&work_dir, &["show", &id.to_string()]);
+    assert!(crash["report"].is_null());
+}
+
+// ─── Crash group tests ────────────────────────────────────────────────────────
+
+#[test]
+fn crashes_with_same_signature_share_a_group() {
+    let work_dir = setup_test_env();
+    let a = seed_crash(&work_dir, Some("crash.ips"));
+    let b = seed_crash(&work_dir, Some("crash.ips"));
+    run_json(&work_dir, &["reparse"]);
+
+    let groups = run_json(&work_dir, &["groups", "list"]);
+    assert_eq!(groups["count"], 1);
+    let group = &groups["groups"][0];
+    assert_eq!(group["crash_count"], 2);
+    assert_eq!(group["device_count"], 1);
+    assert_eq!(group["exception_type"], "EXC_BAD_ACCESS");
+    // Only in-app frames make up the signature
+    assert_eq!(group["frames"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        group["frames"][0],
+        "MyApp: SettingsViewController.viewDidLoad()"
+    );
+
+    let group_id = group["id"].as_i64().unwrap();
+    let crash = run_json(&work_dir, &["show", &a.to_string()]);
+    assert_eq!(crash["group_id"], group_id);
+    assert_eq!(crash["signature"], group["signature"]);
+    assert_eq!(crash["group_hash"], group["hash"]);
+    assert_eq!(
+        group["signature"],
+        "EXC_BAD_ACCESS | MyApp: SettingsViewController.viewDidLoad() | MyApp: @objc SettingsViewController.viewDidLoad()"
+    );
+    assert_eq!(group["hash"].as_str().unwrap().len(), 16);
+    let matched = run_json(
+        &work_dir,
+        &[
+            "list",
+            "--where",
+            r#"signature ~ "%SettingsViewController%""#,
+        ],
+    );
+    assert_eq!(matched["count"], 2);
+
+    let shown = run_json(&work_dir, &["groups", "show", &group_id.to_string()]);
+    let ids: Vec<i64> = shown["crashes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_i64().unwrap())
+        .collect();
+    assert!(ids.contains(&a) && ids.contains(&b));
+    assert_eq!(shown["by_device"][0][0], "iPhone16,2");
+
+    let list = run_json(&work_dir, &["list", "--group", &group_id.to_string()]);
+    assert_eq!(list["count"], 2);
+}
+
+#[test]
+fn groups_show_nonexistent_fails() {
+    let work_dir = setup_test_env();
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .args(["groups", "show", "999"])
+        .output()
+        .expect("groups show failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not found"));
+}
+
+#[test]
+fn groups_fix_closes_member_crashes() {
+    let work_dir = setup_test_env();
+    let a = seed_crash(&work_dir, Some("crash.ips"));
+    seed_crash(&work_dir, Some("crash.ips"));
+    run_json(&work_dir, &["reparse"]);
+    run_json(&work_dir, &["investigate", &a.to_string()]);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute(
+        "INSERT INTO builds (id, app_id, uploaded_at) VALUES ('build-2', 1, '2025-06-20T00:00:00Z')",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    let groups = run_json(&work_dir, &["groups", "list"]);
+    let group_id = groups["groups"][0]["id"].as_i64().unwrap().to_string();
+
+    let fixed = run_json(
+        &work_dir,
+        &[
+            "groups",
+            "fix",
+            &group_id,
+            "--build",
+            "build-2",
+            "--notes",
+            "nil check",
+        ],
+    );
+    assert_eq!(fixed["status"], "fixed");
+    assert_eq!(fixed["fixed_build_id"], "build-2");
+    assert_eq!(fixed["crashes_closed"], 2);
+    assert_eq!(fixed["unfixed"], 0);
+
+    let crash = run_json(&work_dir, &["show", &a.to_string()]);
+    assert_eq!(crash["status"], "fixed");
+    assert_eq!(crash["fix_notes"], "nil check");
+
+    let reopened = run_json(&work_dir, &["groups", "reopen", &group_id]);
+    assert_eq!(reopened["status"], "open");
+    assert!(reopened["fixed_build_id"].is_null());
+}
+
+/// `tests/fixtures/dsyms` holds a minimal arm64 dSYM for the MyApp image in
+/// `unsymbolicated.ips` (UUID 5C1D2E3F-…). It was assembled with `llvm-mc -g`
+/// from a two-function source and its LC_UUID patched via obj2yaml/yaml2obj:
+/// `settings_view_did_load` at 0x0 (offset 0x8 is line 7) and
+/// `objc_view_did_load` at 0x14.
+fn dsym_fixture_dir() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dsyms")
+}
+
+/// Same UUID as `dsym_fixture_dir`, but built with `llc` from IR whose two
+/// subprograms carry linkage names, as Swift and C++ do (assembler `ltmp`
+/// labels dropped from its symbol table, as a linked image would be):
+/// `$s5MyApp22SettingsViewControllerC11viewDidLoadyyF` ("viewDidLoad") at
+/// 0x0 and `_ZN5MyApp13SettingsStore4loadEv` ("load") at 0x14.
+fn mangled_dsym_fixture_dir() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dsyms-mangled")
+}
+
+#[test]
+fn symbolicate_resolves_frames_from_dsym() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("unsymbolicated.ips"));
+    let dsyms = dsym_fixture_dir();
+
+    let out = run_json(
+        &work_dir,
+        &[
+            "symbolicate",
+            &id.to_string(),
+            "--dsym-dir",
+            dsyms.to_str().unwrap(),
+        ],
+    );
+    let result = &out["symbolicated"][0];
+    assert_eq!(result["resolved"], 2);
+    assert_eq!(
+        result["missing_dsyms"],
+        serde_json::json!(["b2c3d4e5-f6a7-3b8c-9d0e-1f2a3b4c5d6e"])
+    );
+    let path = result["symbolicated_log_path"].as_str().unwrap();
+    assert!(path.ends_with(&format!("{id}.symbolicated.ips")));
+    assert!(std::path::Path::new(path).exists());
+
+    let crash = run_json(&work_dir, &["show", &id.to_string()]);
+    assert_eq!(crash["symbolicated_log_path"], path);
+    let frames = &crash["report"]["threads"][0]["frames"];
+    assert_eq!(frames[0]["symbol"], "settings_view_did_load");
+    assert_eq!(frames[0]["symbol_offset"], 8);
+    assert_eq!(
+        frames[0]["source_file"],
+        "/src/MyApp/SettingsViewController.swift"
+    );
+    assert_eq!(frames[0]["source_line"], 7);
+    assert_eq!(frames[1]["symbol"], "objc_view_did_load");
+    assert_eq!(frames[1]["symbol_offset"], 4);
+
+    // The original log is left untouched, and reparse keeps using the copy.
+    let original = std::fs::read_to_string(crash["log_path"].as_str().unwrap()).unwrap();
+    assert!(!original.contains("settings_view_did_load"));
+    run_json(&work_dir, &["reparse", &id.to_string()]);
+    let crash = run_json(&work_dir, &["show", &id.to_string()]);
+    assert_eq!(
+        crash["report"]["threads"][0]["frames"][0]["symbol"],
+        "settings_view_did_load"
+    );
+}
+
+#[test]
+fn symbolicate_prefers_plain_names_over_mangled_ones() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("unsymbolicated.ips")).to_string();
+    let dsyms = mangled_dsym_fixture_dir();
+
+    let out = run_json(
+        &work_dir,
+        &["symbolicate", &id, "--dsym-dir", dsyms.to_str().unwrap()],
+    );
+    let path = out["symbolicated"][0]["symbolicated_log_path"]
+        .as_str()
+        .unwrap();
+    let text = std::fs::read_to_string(path).unwrap();
+    assert!(!text.contains("$s5MyApp") && !text.contains("_ZN5MyApp"));
+
+    let crash = run_json(&work_dir, &["show", &id]);
+    let frames = &crash["report"]["threads"][0]["frames"];
+    assert_eq!(frames[0]["symbol"], "viewDidLoad");
+    assert_eq!(frames[0]["symbol_offset"], 8);
+    assert_eq!(frames[0]["source_line"], 7);
+    assert_eq!(frames[1]["symbol"], "load");
+    assert_eq!(frames[1]["symbol_offset"], 4);
+}
+
+#[test]
+fn symbolicating_a_fixed_group_keeps_its_fix() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("unsymbolicated.ips")).to_string();
+    run_json(&work_dir, &["reparse"]);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute(
+        "INSERT INTO builds (id, app_id, uploaded_at) VALUES ('build-2', 1, '2025-06-20T00:00:00Z')",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+    let old_group = run_json(&work_dir, &["show", &id])["group_id"].clone();
+    let old_id = old_group.as_i64().unwrap().to_string();
+    run_json(
+        &work_dir,
+        &[
+            "groups",
+            "fix",
+            &old_id,
+            "--build",
+            "build-2",
+            "--notes",
+            "nil check",
+        ],
+    );
+
+    let dsyms = dsym_fixture_dir();
+    run_json(
+        &work_dir,
+        &["symbolicate", &id, "--dsym-dir", dsyms.to_str().unwrap()],
+    );
+    let crash = run_json(&work_dir, &["show", &id]);
+    assert_ne!(crash["group_id"], old_group, "signature should change");
+    let group = run_json(
+        &work_dir,
+        &[
+            "groups",
+            "show",
+            &crash["group_id"].as_i64().unwrap().to_string(),
+        ],
+    );
+    assert_eq!(group["status"], "fixed");
+    assert_eq!(group["fixed_build_id"], "build-2");
+    assert_eq!(group["fix_notes"], "nil check");
+    assert!(group["fixed_at"].is_string());
+
+    // The old signature stays fixed too, for crashes not yet symbolicated.
+    let old = run_json(&work_dir, &["groups", "show", &old_id]);
+    assert_eq!(old["status"], "fixed");
+}
+
+#[test]
+fn symbolicate_uses_configured_dsym_dir() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("unsymbolicated.ips"));
+
+    let config_path = work_dir.path().join("asc-crashes/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str(&format!(
+        "\n[symbolication]\ndsym_dir = {:?}\n",
+        dsym_fixture_dir().to_str().unwrap()
+    ));
+    std::fs::write(&config_path, config).unwrap();
+
+    let out = run_json(&work_dir, &["symbolicate"]);
+    assert_eq!(out["symbolicated"][0]["id"], id);
+    assert_eq!(out["symbolicated"][0]["resolved"], 2);
+}
+
+#[test]
+fn symbolicate_without_dsym_dir_fails() {
+    let work_dir = setup_test_env();
+    seed_crash(&work_dir, Some("unsymbolicated.ips"));
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .arg("symbolicate")
+        .output()
+        .expect("symbolicate failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no dSYM directory configured"));
+}
+
+#[test]
+fn crashes_show_build_version_and_number() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, None);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute(
+        "INSERT INTO builds (id, app_id, version, build_number, uploaded_at)
+         VALUES ('build-1', 1, '2.4.0', '415', '2025-06-10T12:00:00+00:00')",
+        [],
+    )
+    .unwrap();
+    conn.execute(
+        "UPDATE crashes SET build_id = 'build-1' WHERE id = ?1",
+        [id],
+    )
+    .unwrap();
+
+    let list = run_json(&work_dir, &["list"]);
+    assert_eq!(list["crashes"][0]["app_version"], "2.4.0");
+    assert_eq!(list["crashes"][0]["build_number"], "415");
+
+    let crash = run_json(&work_dir, &["show", &id.to_string()]);
+    assert_eq!(crash["build"]["uploaded_at"], "2025-06-10T12:00:00+00:00");
+
+    let stats = run_json(&work_dir, &["stats"]);
+    assert_eq!(stats["by_build"], serde_json::json!([["2.4.0 (415)", 1]]));
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .arg("list")
+        .output()
+        .expect("list failed");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("2.4.0 (415)"));
+}
+
+#[test]
+fn crash_build_falls_back_to_parsed_log() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, Some("crash.ips"));
+    run_json(&work_dir, &["reparse"]);
+
+    let crash = run_json(&work_dir, &["show", &id.to_string()]);
+    assert_eq!(crash["app_version"], "2.3.1");
+    assert_eq!(crash["build_number"], "412");
+    assert!(crash["build"].is_null());
+}
+
+#[test]
+fn builds_report_compares_signatures_with_previous_build() {
+    let work_dir = setup_test_env();
+    let old = seed_crash(&work_dir, Some("crash.ips"));
+    let new = seed_crash(&work_dir, Some("unsymbolicated.ips"));
+    let back = seed_crash(&work_dir, Some("crash.ips"));
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute_batch(
+        "INSERT INTO builds (id, app_id, version, build_number, uploaded_at) VALUES
+             ('build-1', 1, '2.3.1', '412', '2025-06-01T00:00:00+00:00'),
+             ('build-2', 1, '2.3.2', '415', '2025-06-11T00:00:00+00:00'),
+             ('build-fb', 1, '2.3.2', '416', '2025-06-13T00:00:00+00:00'),
+             ('build-3', 1, '2.3.3', '420', '2025-06-15T00:00:00+00:00');
+         INSERT INTO feedbacks (app_id, submission_id, created_at, build_id)
+             VALUES (1, 'fb-1', '2025-06-14T00:00:00+00:00', 'build-fb');",
+    )
+    .unwrap();
+    for (id, build) in [(old, "build-1"), (new, "build-2"), (back, "build-3")] {
+        conn.execute(
+            "UPDATE crashes SET build_id = ?1 WHERE id = ?2",
+            rusqlite::params![build, id],
+        )
+        .unwrap();
+    }
+    drop(conn);
+    run_json(&work_dir, &["reparse"]);
+    let fixed_group = run_json(&work_dir, &["show", &old.to_string()])["group_id"]
+        .as_i64()
+        .unwrap();
+    run_json(
+        &work_dir,
+        &[
+            "groups",
+            "fix",
+            &fixed_group.to_string(),
+            "--build",
+            "build-2",
+        ],
+    );
+
+    let report = run_json(&work_dir, &["builds", "report"]);
+    assert_eq!(report["count"], 4);
+    let ids = |sigs: &serde_json::Value| -> Vec<i64> {
+        sigs.as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["group_id"].as_i64().unwrap())
+            .collect()
+    };
+
+    // The group fixed in build-2 is back in build-3; the feedback-only
+    // build in between is skipped when comparing.
+    let latest = &report["builds"][0];
+    assert_eq!(latest["label"], "2.3.3 (420)");
+    assert_eq!(latest["previous_build_id"], "build-2");
+    assert_eq!(ids(&latest["new_signatures"]), [fixed_group]);
+    assert_eq!(
+        latest["disappeared_signatures"].as_array().unwrap().len(),
+        1
+    );
+    assert_eq!(ids(&latest["regressed_signatures"]), [fixed_group]);
+    assert!(latest["new_signatures"][0]["signature"]
+        .as_str()
+        .unwrap()
+        .starts_with("EXC_BAD_ACCESS | "));
+    assert_eq!(latest["regression"], true);
+
+    let feedback_only = &report["builds"][1];
+    assert_eq!(feedback_only["build_id"], "build-fb");
+    assert_eq!(feedback_only["feedback_count"], 1);
+    assert!(feedback_only["previous_build_id"].is_null());
+    assert_eq!(feedback_only["new_signatures"], serde_json::json!([]));
+    assert_eq!(
+        feedback_only["disappeared_signatures"],
+        serde_json::json!([])
+    );
+    assert_eq!(feedback_only["regression"], false);
+
+    // A signature that's merely new isn't a regression.
+    let second = &report["builds"][2];
+    assert_eq!(second["label"], "2.3.2 (415)");
+    assert_eq!(second["previous_build_id"], "build-1");
+    assert_eq!(second["crash_count"], 1);
+    assert_eq!(second["new_signatures"].as_array().unwrap().len(), 1);
+    assert_eq!(ids(&second["disappeared_signatures"]), [fixed_group]);
+    assert_eq!(second["regression"], false);
+
+    let first = &report["builds"][3];
+    assert_eq!(first["label"], "2.3.1 (412)");
+    assert!(first["previous_build_id"].is_null());
+    assert_eq!(first["regressed_signatures"], serde_json::json!([]));
+    assert_eq!(first["regression"], false);
+    // One crash over the ten days before build-2 shipped.
+    assert_eq!(first["crashes_per_day"], 0.1);
+
+    let text = run_stdout(&work_dir, &["builds", "report"]);
+    assert!(text.contains("2.3.3 (420)"));
+    assert!(text.contains("REGRESSION"));
+    assert!(text.contains(&format!("! #{fixed_group:<4} EXC_BAD_ACCESS @ ")));
+}
+
+#[test]
+fn builds_report_unknown_app_fails() {
+    let work_dir = setup_test_env();
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .args(["builds", "report", "--app", "com.other.app"])
+        .output()
+        .expect("builds report failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not found"));
+}
+
+#[test]
+fn retry_config_rejects_zero_attempts() {
+    let work_dir = setup_test_env();
+    let config_path = work_dir.path().join("asc-crashes/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("\n[retry]\nmax_attempts = 0\n");
+    std::fs::write(&config_path, config).unwrap();
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .arg("list")
+        .output()
+        .expect("list failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("max_attempts"));
+}
+
+#[test]
+fn json_format_reports_errors_as_json() {
+    let work_dir = setup_test_env();
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .args(["show", "999", "--format", "json"])
+        .output()
+        .expect("show failed");
+
+    assert_eq!(output.status.code(), Some(1));
+    let err: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(err["kind"], "error");
+    assert_eq!(err["exit_code"], 1);
+    assert!(err["message"].as_str().unwrap().contains("not found"));
+    assert_eq!(err["errors"], serde_json::json!([]));
+}
+
+// ─── Mock App Store Connect tests ─────────────────────────────────────────────
+
+/// A data dir whose config talks to `mock` with a real (test-only) key and
+/// near-instant retries.
+fn setup_mock_env(mock: &MockAsc) -> tempfile::TempDir {
+    let work_dir = tempfile::TempDir::new().unwrap();
+    bin()
+        .arg("init")
+        .current_dir(work_dir.path())
+        .output()
+        .expect("init failed");
+
+    let key =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/AuthKey_TEST.p8");
+    let config = format!(
+        r#"
+[api]
+issuer_id = "test-issuer"
+key_id = "TESTKEY123"
+private_key = "{}"
+base_url = "{}"
+
+[retry]
+max_attempts = 3
+base_delay_ms = 1
+max_delay_ms = 10
+
+[[apps]]
+bundle_id = "com.test.app"
+"#,
+        key.display(),
+        mock.url()
+    );
+    std::fs::write(work_dir.path().join("asc-crashes/config.toml"), config).unwrap();
+    work_dir
+}
+
+fn run_failing(work_dir: &tempfile::TempDir, args: &[&str]) -> (i32, serde_json::Value) {
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .args(args)
+        // Keep retry warnings out of the JSON error on stderr.
+        .env("RUST_LOG", "off")
+        .output()
+        .expect("failed to run");
+    assert!(!output.status.success(), "{args:?} unexpectedly succeeded");
+    let err = serde_json::from_slice(&output.stderr).unwrap_or_else(|_| {
+        panic!(
+            "stderr is not JSON: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code().unwrap(), err)
+}
+
+#[test]
+fn apps_uses_base_url_from_env() {
+    let mock = MockAsc::with_fixtures();
+    let work_dir = setup_mock_env(&mock);
+    let config_path = work_dir.path().join("asc-crashes/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace(mock.url(), "http://127.0.0.1:9"),
+    )
+    .unwrap();
+
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .args(["apps", "--format", "json"])
+        .env("ASC_BASE_URL", mock.url())
+        .output()
+        .expect("apps failed");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let apps: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(apps[0]["id"], support::APP_ID);
+    assert_eq!(apps[0]["bundle_id"], "com.test.app");
+    assert_eq!(mock.hits("/v1/apps"), 1);
+}
+
+#[test]
+fn sync_pages_through_crashes_and_downloads_files() {
+    let mock = MockAsc::with_fixtures();
+    let work_dir = setup_mock_env(&mock);
+
+    let out = run_json(&work_dir, &["sync"]);
+    let crashes = out["new_crashes"].as_array().unwrap();
+    assert_eq!(crashes.len(), 3);
+    assert_eq!(
+        mock.hits("/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=2"),
+        1
+    );
+    assert!(crashes
+        .iter()
+        .all(|c| c["app_version"] == "2.3.1" && c["build_number"] == "412"));
+    let without_log: Vec<_> = crashes.iter().filter(|c| c["has_log"] == false).collect();
+    assert_eq!(without_log.len(), 1);
+    assert_eq!(without_log[0]["device_model"], "iPad14,1");
+
+    let feedbacks = out["new_feedbacks"].as_array().unwrap();
+    assert_eq!(feedbacks.len(), 1);
+    let shot = feedbacks[0]["screenshot_path"].as_str().unwrap();
+    assert!(shot.ends_with(".png"));
+    assert!(std::path::Path::new(shot).exists());
+    assert_eq!(mock.hits("/v1/builds/build-412"), 1);
+
+    // Second run: the first page reaches back past the last sync's newest
+    // crash, so pagination stops there, and the log that wasn't ready
+    // before is picked up.
+    mock.on(
+        "/v1/betaFeedbackCrashSubmissions/crash-2/crashLog",
+        Reply::fixture("crash_log.json"),
+    );
+    let out = run_json(&work_dir, &["sync"]);
+    assert_eq!(out["new_crashes"], serde_json::json!([]));
+    assert_eq!(out["recovered_logs"].as_array().unwrap().len(), 1);
+    assert_eq!(out["crash_total"], 3);
+    assert_eq!(
+        mock.hits("/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=2"),
+        1
+    );
+    assert_eq!(mock.hits("/v1/builds/build-412"), 1);
+}
+
+/// Serve `page` as the next crash page, with logs that fall into the
+/// fixtures' crash group.
+fn next_crash_page(mock: &MockAsc, page: &[(&str, &str, Option<&str>)]) {
+    mock.on(
+        &format!("/v1/apps/{}/betaFeedbackCrashSubmissions", support::APP_ID),
+        Reply::crash_page(page),
+    );
+    for (id, _, _) in page {
+        mock.on(
+            &format!("/v1/betaFeedbackCrashSubmissions/{id}/crashLog"),
+            Reply::fixture("crash_log.json"),
+        );
+    }
+}
+
+#[test]
+fn sync_triages_new_crashes_against_fixed_groups() {
+    let mock = MockAsc::with_fixtures();
+    mock.on(
+        "/v1/builds/build-411",
+        Reply::build("build-411", "411", "2025-06-01T08:00:00Z"),
+    )
+    .on(
+        "/v1/builds/build-413",
+        Reply::build("build-413", "413", "2025-06-18T08:00:00Z"),
+    );
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync"]);
+    let groups = run_json(&work_dir, &["groups", "list"]);
+    assert_eq!(groups["count"], 1);
+    let group_id = groups["groups"][0]["id"].as_i64().unwrap();
+    let first = run_json(
+        &work_dir,
+        &["list", "--where", r#"submission_id = "crash-1""#],
+    );
+    let first_id = first["crashes"][0]["id"].as_i64().unwrap();
+    run_json(
+        &work_dir,
+        &[
+            "groups",
+            "fix",
+            &group_id.to_string(),
+            "--build",
+            "build-412",
+        ],
+    );
+
+    // A late report from a build older than the fix is a duplicate.
+    next_crash_page(
+        &mock,
+        &[("crash-old", "2025-06-15T10:00:00Z", Some("build-411"))],
+    );
+    let out = run_json(&work_dir, &["sync"]);
+    let dups = out["auto_duplicates"].as_array().unwrap();
+    assert_eq!(dups.len(), 1, "{out}");
+    assert_eq!(dups[0]["duplicate_of"], first_id);
+    assert_eq!(dups[0]["group_id"], group_id);
+    assert_eq!(dups[0]["fixed_build_id"], "build-412");
+    assert_eq!(out["regressions"], serde_json::json!([]));
+    let dup = run_json(&work_dir, &["show", &dups[0]["id"].to_string()]);
+    assert_eq!(dup["status"], "duplicate");
+    assert_eq!(dup["duplicate_of"], first_id);
+
+    // Without a build there's nothing to order against: left alone, reported.
+    next_crash_page(&mock, &[("crash-nobuild", "2025-06-16T10:00:00Z", None)]);
+    let out = run_json(&work_dir, &["sync"]);
+    let untriaged = out["untriaged"].as_array().unwrap();
+    assert_eq!(untriaged.len(), 1, "{out}");
+    assert_eq!(untriaged[0]["group_id"], group_id);
+    assert_eq!(out["regressions"], serde_json::json!([]));
+    assert_eq!(out["auto_duplicates"], serde_json::json!([]));
+    let left = run_json(&work_dir, &["show", &untriaged[0]["id"].to_string()]);
+    assert_eq!(left["status"], "new");
+    assert!(left["regression_of"].is_null());
+
+    // A crash from a build after the fix is a regression.
+    next_crash_page(
+        &mock,
+        &[("crash-new", "2025-06-20T10:00:00Z", Some("build-413"))],
+    );
+    let out = run_json(&work_dir, &["sync"]);
+    let regressions = out["regressions"].as_array().unwrap();
+    assert_eq!(regressions.len(), 1, "{out}");
+    assert_eq!(regressions[0]["group_id"], group_id);
+    assert_eq!(regressions[0]["fixed_build_id"], "build-412");
+    let group = run_json(&work_dir, &["groups", "show", &group_id.to_string()]);
+    assert_eq!(group["status"], "regressed");
+    let flagged = run_json(&work_dir, &["list", "--regressions"]);
+    assert_eq!(flagged["count"], 1);
+    assert_eq!(flagged["crashes"][0]["id"], regressions[0]["id"]);
+    assert_eq!(flagged["crashes"][0]["regression_of"], group_id);
+}
+
+#[test]
+fn groups_fix_checks_the_build_exists() {
+    let mock = MockAsc::with_fixtures();
+    mock.on(
+        "/v1/builds/build-413",
+        Reply::build("build-413", "413", "2025-06-18T08:00:00Z"),
+    );
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync"]);
+    let groups = run_json(&work_dir, &["groups", "list"]);
+    let group_id = groups["groups"][0]["id"].as_i64().unwrap().to_string();
+
+    let (code, err) = run_failing(
+        &work_dir,
+        &["groups", "fix", &group_id, "--build", "bulid-413"],
+    );
+    assert_eq!(code, 1);
+    let message = err["message"].as_str().unwrap();
+    assert!(message.contains("build 'bulid-413' not found"), "{message}");
+    let group = run_json(&work_dir, &["groups", "show", &group_id]);
+    assert_eq!(group["status"], "open");
+
+    // A fix build nobody has crashed on yet is looked up and remembered.
+    let fixed = run_json(
+        &work_dir,
+        &["groups", "fix", &group_id, "--build", "build-413"],
+    );
+    assert_eq!(fixed["fixed_build_id"], "build-413");
+    assert_eq!(mock.hits("/v1/builds/build-413"), 1);
+}
+
+#[test]
+fn sync_downloads_logs_jobs_at_a_time() {
+    let logs = ["crash-1", "crash-2", "crash-3"];
+    // (--jobs, [sync] concurrency, expected parallel downloads)
+    for (jobs, concurrency, expected) in [
+        (Some("1"), None, 1),
+        (Some("4"), None, 3),
+        (None, Some(2), 2),
+    ] {
+        let mock = MockAsc::with_fixtures();
+        for id in logs {
+            mock.on(
+                &format!("/v1/betaFeedbackCrashSubmissions/{id}/crashLog"),
+                Reply::fixture("crash_log.json").delayed(150),
+            );
+        }
+        let work_dir = setup_mock_env(&mock);
+        if let Some(n) = concurrency {
+            let path = work_dir.path().join("asc-crashes/config.toml");
+            let config = std::fs::read_to_string(&path).unwrap();
+            std::fs::write(&path, format!("{config}\n[sync]\nconcurrency = {n}\n")).unwrap();
+        }
+        let mut args = vec!["sync"];
+        if let Some(n) = jobs {
+            args.extend(["--jobs", n]);
+        }
+
+        let out = run_json(&work_dir, &args);
+        let crashes = out["new_crashes"].as_array().unwrap();
+        assert!(crashes.iter().all(|c| c["has_log"] == true), "{out}");
+        assert_eq!(mock.max_in_flight(), expected, "{args:?}");
+    }
+}
+
+#[test]
+fn sync_rejects_zero_jobs() {
+    let work_dir = setup_test_env();
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .args(["sync", "--jobs", "0"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid value '0' for '--jobs"));
+}
+
+#[test]
+fn sync_retries_rate_limited_requests() {
+    let mock = MockAsc::with_fixtures();
+    mock.on("/v1/apps", Reply::rate_limited())
+        .then("/v1/apps", Reply::fixture("apps.json"));
+    let work_dir = setup_mock_env(&mock);
+
+    let out = run_json(&work_dir, &["sync", "--no-feedback"]);
+    assert_eq!(out["new_crashes"].as_array().unwrap().len(), 3);
+    assert_eq!(mock.hits("/v1/apps"), 2);
+}
+
+#[test]
+fn sync_gives_up_when_still_rate_limited() {
+    let mock = MockAsc::with_fixtures();
+    mock.on("/v1/apps", Reply::rate_limited());
+    let work_dir = setup_mock_env(&mock);
+
+    let (code, err) = run_failing(&work_dir, &["sync"]);
+    assert_eq!(code, 6);
+    assert_eq!(err["kind"], "rate_limited");
+    assert_eq!(err["errors"][0]["code"], "RATE_LIMIT_EXCEEDED");
+    assert_eq!(mock.hits("/v1/apps"), 3);
+}
+
+#[test]
+fn apps_with_rejected_key_exits_unauthorized() {
+    let mock = MockAsc::start();
+    mock.on("/v1/apps", Reply::fixture("error_401.json").status(401));
+    let work_dir = setup_mock_env(&mock);
+
+    let (code, err) = run_failing(&work_dir, &["apps"]);
+    assert_eq!(code, 3);
+    assert_eq!(err["kind"], "unauthorized");
+    assert_eq!(err["status"], 401);
+    assert_eq!(err["errors"][0]["code"], "NOT_AUTHORIZED");
+    assert_eq!(mock.hits("/v1/apps"), 1);
+}
+
+#[test]
+fn sync_signs_one_token_and_reuses_it() {
+    let mock = MockAsc::with_fixtures();
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync"]);
+
+    let tokens = mock.tokens();
+    assert!(tokens.len() > 5, "{tokens:?}");
+    assert!(tokens.iter().all(|t| *t == tokens[0] && !t.is_empty()));
+}
+
+#[test]
+fn sync_signs_a_new_token_near_expiry() {
+    let mock = MockAsc::with_fixtures();
+    for id in ["crash-1", "crash-2", "crash-3"] {
+        mock.on(
+            &format!("/v1/betaFeedbackCrashSubmissions/{id}/crashLog"),
+            Reply::fixture("crash_log.json").delayed(1_100),
+        );
+    }
+    let work_dir = setup_mock_env(&mock);
+    // Tokens live 62s and are replaced 60s before expiry: after ~2s.
+    let path = work_dir.path().join("asc-crashes/config.toml");
+    let config = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(
+        &path,
+        config.replace("[retry]", "token_ttl_secs = 62\n\n[retry]"),
+    )
+    .unwrap();
+
+    run_json(&work_dir, &["sync", "--jobs", "1"]);
+    let tokens = mock.tokens();
+    let mut runs = tokens.clone();
+    runs.dedup();
+    assert!(runs.len() >= 2, "{tokens:?}");
+    // Each token is reused until it's replaced, and never comes back.
+    let distinct: std::collections::HashSet<&String> = runs.iter().collect();
+    assert_eq!(distinct.len(), runs.len(), "{tokens:?}");
+    assert_eq!(tokens[0], tokens[1]);
+}
+
+#[test]
+fn rejected_token_is_not_reused() {
+    let mock = MockAsc::with_fixtures();
+    mock.on(
+        "/v1/betaFeedbackCrashSubmissions/crash-3/crashLog",
+        Reply::fixture("error_401.json").status(401),
+    );
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync", "--jobs", "1"]);
+
+    let tokens = mock.tokens();
+    let rejected = mock.first_request("/v1/betaFeedbackCrashSubmissions/crash-3/crashLog");
+    assert!(tokens[..=rejected].iter().all(|t| *t == tokens[0]));
+    assert!(
+        tokens[rejected + 1..].iter().all(|t| *t != tokens[0]),
+        "{tokens:?}"
+    );
+    assert!(tokens.len() > rejected + 1);
+}
+
+#[test]
+fn token_ttl_must_fit_apples_limit() {
+    let mock = MockAsc::start();
+    let work_dir = setup_mock_env(&mock);
+    let path = work_dir.path().join("asc-crashes/config.toml");
+    let config = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(
+        &path,
+        config.replace("[retry]", "token_ttl_secs = 3600\n\n[retry]"),
+    )
+    .unwrap();
+
+    let (code, err) = run_failing(&work_dir, &["apps"]);
+    assert_eq!(code, 1);
+    let message = err["message"].as_str().unwrap();
+    assert!(
+        message.contains("token_ttl_secs must be between 61 and 1200"),
+        "{message}"
+    );
+}
+
+#[test]
+fn sync_unknown_app_exits_not_found() {
+    let mock = MockAsc::with_fixtures();
+    mock.on("/v1/apps", Reply::json(r#"{"data":[]}"#));
+    let work_dir = setup_mock_env(&mock);
+
+    let (code, err) = run_failing(&work_dir, &["sync"]);
+    assert_eq!(code, 5);
+    assert_eq!(err["kind"], "not_found");
+    assert!(err["message"].as_str().unwrap().contains("com.test.app"));
+}
+
+#[test]
+fn sync_error_survives_a_failure_to_record_the_run() {
+    let mock = MockAsc::with_fixtures();
+    mock.on("/v1/apps", Reply::json(r#"{"data":[]}"#));
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync", "history"]);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TRIGGER no_finish BEFORE UPDATE ON sync_runs
+         BEGIN SELECT RAISE(FAIL, 'sync_runs is read-only'); END;",
+    )
+    .unwrap();
+    drop(conn);
+
+    let (code, err) = run_failing(&work_dir, &["sync"]);
+    assert_eq!(code, 5);
+    assert_eq!(err["kind"], "not_found");
+    assert!(err["message"].as_str().unwrap().contains("com.test.app"));
+
+    // With nothing else wrong, the bookkeeping failure is the error.
+    mock.on("/v1/apps", Reply::fixture("apps.json"));
+    let (code, err) = run_failing(&work_dir, &["sync"]);
+    assert_eq!(code, 1);
+    let message = err["message"].as_str().unwrap();
+    assert!(message.contains("failed to record sync run"), "{message}");
+}
+
+#[test]
+fn sync_resumes_pagination_after_an_interrupted_run() {
+    let page2 = "/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=2";
+    let mock = MockAsc::with_fixtures();
+    mock.on(page2, Reply::json("{}").status(503));
+    let work_dir = setup_mock_env(&mock);
+
+    let (code, _) = run_failing(&work_dir, &["sync", "--no-feedback"]);
+    assert_eq!(code, 7);
+
+    // Page 1 is all known now, but no sync has finished, so keep going.
+    mock.on(page2, Reply::fixture("crashes_page2.json"));
+    let out = run_json(&work_dir, &["sync", "--no-feedback"]);
+    assert_eq!(out["new_crashes"].as_array().unwrap().len(), 1);
+    assert_eq!(out["new_crashes"][0]["submission_id"], "crash-1");
+    assert_eq!(out["crash_total"], 3);
+}
+
+#[test]
+fn sync_cut_short_by_a_repeated_page_keeps_the_high_water_mark() {
+    let crashes = format!("/v1/apps/{}/betaFeedbackCrashSubmissions", support::APP_ID);
+    let page2 = std::fs::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/asc/crashes_page2.json"),
+    )
+    .unwrap();
+    let mock = MockAsc::with_fixtures();
+    mock.on(
+        &format!("{crashes}?cursor=2"),
+        Reply::json(&page2.replace("\"self\":", "\"next\":")),
+    );
+    let work_dir = setup_mock_env(&mock);
+
+    let out = run_json(&work_dir, &["sync", "--no-feedback"]);
+    assert_eq!(out["sweeps"][0]["complete"], false);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    let saved: i64 = conn
+        .query_row("SELECT COUNT(*) FROM sync_state", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(saved, 0);
+    drop(conn);
+
+    // The page after the repeat turns up; the next sync must still reach it.
+    mock.on(
+        &format!("{crashes}?cursor=2"),
+        Reply::json(&page2.replace(
+            "\"self\": \"{base}/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=2\"",
+            "\"next\": \"{base}/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=3\"",
+        )),
+    )
+    .on(
+        &format!("{crashes}?cursor=3"),
+        Reply::crash_page(&[("crash-0", "2025-06-01T08:00:00Z", None)]),
+    )
+    .on(
+        "/v1/betaFeedbackCrashSubmissions/crash-0/crashLog",
+        Reply::fixture("crash_log.json"),
+    );
+    let out = run_json(&work_dir, &["sync", "--no-feedback"]);
+    let sweep = &out["sweeps"][0];
+    assert_eq!(sweep["full"], true);
+    assert_eq!(sweep["complete"], true);
+    assert_eq!(sweep["pages"], 3);
+    assert_eq!(out["new_crashes"][0]["submission_id"], "crash-0");
+}
+
+#[test]
+fn sync_full_picks_up_backfilled_submissions() {
+    let page2 = "/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=2";
+    let mock = MockAsc::with_fixtures();
+    let work_dir = setup_mock_env(&mock);
+    let out = run_json(&work_dir, &["sync", "--no-feedback"]);
+    assert_eq!(out["sweeps"][0]["full"], true);
+
+    mock.on(page2, Reply::fixture("crashes_page2_backfilled.json"));
+    let out = run_json(&work_dir, &["sync", "--no-feedback"]);
+    assert_eq!(out["new_crashes"], serde_json::json!([]));
+    assert_eq!(out["sweeps"][0]["full"], false);
+    assert_eq!(out["sweeps"][0]["pages"], 1);
+
+    let out = run_json(&work_dir, &["sync", "--no-feedback", "--full"]);
+    let sweep = &out["sweeps"][0];
+    assert_eq!(sweep["full"], true);
+    assert_eq!(sweep["pages"], 2);
+    assert_eq!(sweep["seen"], 4);
+    assert_eq!(sweep["new"], 1);
+    assert_eq!(sweep["reconciled"], 1);
+    assert_eq!(out["new_crashes"][0]["submission_id"], "crash-0");
+}
+
+#[test]
+fn full_sync_flags_submissions_gone_from_app_store_connect() {
+    let page2 = "/v1/apps/1000000001/betaFeedbackCrashSubmissions?cursor=2";
+    let mock = MockAsc::with_fixtures();
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync", "--no-feedback"]);
+
+    // crash-1 expired on Apple's side.
+    mock.on(page2, Reply::json(r#"{"data":[],"links":{}}"#));
+    let out = run_json(&work_dir, &["sync", "--no-feedback", "--full"]);
+    assert_eq!(out["sweeps"][0]["remote_deleted"], 1);
+
+    let listed = run_json(&work_dir, &["list"]);
+    assert_eq!(listed["count"], 2);
+    let expired = run_json(&work_dir, &["list", "--only-expired"]);
+    assert_eq!(expired["count"], 1);
+    assert_eq!(expired["crashes"][0]["submission_id"], "crash-1");
+    assert!(expired["crashes"][0]["remote_deleted_at"].is_string());
+    let all = run_json(&work_dir, &["list", "--include-expired"]);
+    assert_eq!(all["count"], 3);
+
+    mock.on(page2, Reply::fixture("crashes_page2.json"));
+    let out = run_json(&work_dir, &["sync", "--no-feedback", "--full"]);
+    assert_eq!(out["sweeps"][0]["restored"], 1);
+    assert_eq!(run_json(&work_dir, &["list", "--only-expired"])["count"], 0);
+}
+
+#[test]
+fn sync_history_records_each_run() {
+    let mock = MockAsc::with_fixtures();
+    let work_dir = setup_mock_env(&mock);
+    run_json(&work_dir, &["sync"]);
+
+    mock.on("/v1/apps", Reply::fixture("error_401.json").status(401));
+    let (code, _) = run_failing(&work_dir, &["sync", "--full"]);
+    assert_eq!(code, 3);
+
+    let history = run_json(&work_dir, &["sync", "history"]);
+    assert_eq!(history["count"], 2);
+    let failed = &history["runs"][0];
+    assert_eq!(failed["status"], "failed");
+    assert_eq!(failed["exit_code"], 3);
+    assert_eq!(failed["full"], true);
+    assert!(failed["errors"][0]
+        .as_str()
+        .unwrap()
+        .contains("NOT_AUTHORIZED"));
+
+    let ok = &history["runs"][1];
+    assert_eq!(ok["status"], "ok");
+    assert_eq!(ok["exit_code"], 0);
+    assert_eq!(ok["apps"], serde_json::json!(["com.test.app"]));
+    assert_eq!(ok["pages"], 3);
+    assert_eq!(ok["new_crashes"], 3);
+    assert_eq!(ok["new_feedbacks"], 1);
+    assert_eq!(ok["logs_downloaded"], 2);
+    assert_eq!(ok["screenshots_downloaded"], 1);
+    assert_eq!(ok["errors"], serde_json::json!([]));
+    assert!(ok["finished_at"].is_string());
+}
+
+// ─── Triage workflow tests ────────────────────────────────────────────────────
+
+#[test]
+fn status_changes_are_recorded_in_history() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, None).to_string();
+    let data_dir = work_dir.path().join("asc-crashes");
+    for args in [
+        vec!["fix", &id, "--notes", "nil check"],
+        vec!["reopen", &id],
+    ] {
+        let status = bin()
+            .env("ASC_ACTOR", "alice")
+            .args(["--data-dir", data_dir.to_str().unwrap()])
+            .args(&args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    let crash = run_json(&work_dir, &["show", &id]);
+    assert!(crash["fix_notes"].is_null());
+    let history = crash["history"].as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0]["old_status"], "new");
+    assert_eq!(history[0]["new_status"], "fixed");
+    assert_eq!(history[0]["notes"], "nil check");
+    assert_eq!(history[0]["actor"], "alice");
+    assert_eq!(history[1]["old_status"], "fixed");
+    assert_eq!(history[1]["new_status"], "new");
+    assert_eq!(history[1]["notes"], "cleared notes: nil check");
+    assert!(history[1]["created_at"].is_string());
+}
+
+#[test]
+fn notes_accumulate_on_a_crash() {
+    let work_dir = setup_test_env();
+    let id = seed_crash(&work_dir, None).to_string();
+    run_json(&work_dir, &["note", &id, "Repro on iPad only"]);
+    run_json(&work_dir, &["fix", &id, "--notes", "nil check"]);
+    let added = run_json(&work_dir, &["note", &id, "Confirmed in 2.3.2"]);
+    assert_eq!(added["entity"], "crash");
+    assert_eq!(added["body"], "Confirmed in 2.3.2");
+
+    let crash = run_json(&work_dir, &["show", &id]);
+    let comments = crash["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["body"], "Repro on iPad only");
+    assert_eq!(comments[1]["body"], "Confirmed in 2.3.2");
+    assert_eq!(crash["fix_notes"], "nil check");
+
+    let (code, err) = run_failing(&work_dir, &["feedback", "note", "999", "hello"]);
+    assert_eq!(code, 1);
+    assert!(err["message"].as_str().unwrap().contains("not found"));
+}
+
+#[test]
+fn assigned_crashes_form_per_person_queues() {
+    let work_dir = setup_test_env();
+    let a = seed_crash(&work_dir, None).to_string();
+    let b = seed_crash(&work_dir, None).to_string();
+    let c = seed_crash(&work_dir, None).to_string();
+    let assigned = run_json(&work_dir, &["assign", &a, "--to", "alice"]);
+    assert_eq!(assigned["assignee"], "alice");
+    run_json(&work_dir, &["assign", &b, "--to", "bob"]);
+    run_json(&work_dir, &["assign", &c, "--to", "alice"]);
+    run_json(&work_dir, &["assign", &c, "--none"]);
+    let (code, err) = run_failing(&work_dir, &["assign", &a, "--to", "  "]);
+    assert_eq!(code, 1);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("assignee is empty"));
+
+    let alice = run_json(&work_dir, &["list", "--assignee", "alice"]);
+    assert_eq!(alice["count"], 1);
+    assert_eq!(alice["crashes"][0]["id"].to_string(), a);
+    let unassigned = run_json(&work_dir, &["list", "--unassigned"]);
+    assert_eq!(unassigned["count"], 1);
+    assert_eq!(unassigned["crashes"][0]["id"].to_string(), c);
+
+    run_json(&work_dir, &["fix", &b]);
+    let stats = run_json(&work_dir, &["stats"]);
+    assert_eq!(
+        stats["by_assignee"],
+        serde_json::json!([["alice", 1], ["(unassigned)", 1]])
+    );
+}
+
+#[test]
+fn tags_filter_lists_with_any_or_all_semantics() {
+    let work_dir = setup_test_env();
+    let a = seed_crash(&work_dir, None).to_string();
+    let b = seed_crash(&work_dir, None).to_string();
+    seed_crash(&work_dir, None);
+    let tagged = run_json(&work_dir, &["tag", &a, "Networking", "p0"]);
+    assert_eq!(tagged["tags"], serde_json::json!(["networking", "p0"]));
+    run_json(&work_dir, &["tag", &b, "networking,ios18-only"]);
+
+    let any = run_json(&work_dir, &["list", "--tag", "p0,ios18-only"]);
+    assert_eq!(any["count"], 2);
+    let all = run_json(
+        &work_dir,
+        &["list", "--tag", "networking", "--tag", "p0", "--all-tags"],
+    );
+    assert_eq!(all["count"], 1);
+    assert_eq!(all["crashes"][0]["id"].to_string(), a);
+
+    let untagged = run_json(&work_dir, &["untag", &a, "p0"]);
+    assert_eq!(untagged["tags"], serde_json::json!(["networking"]));
+    let stats = run_json(&work_dir, &["stats"]);
+    assert_eq!(
+        stats["by_tag"],
+        serde_json::json!([["networking", 2], ["ios18-only", 1]])
+    );
+}
+
+// ─── Workflow tests ───────────────────────────────────────────────────────────
+
+const WORKFLOW: &str = r#"
+[workflow]
+statuses = ["new", "investigating", "needs-info", "fixed", "fixed-pending-release", "wontfix", "duplicate"]
+open = ["new", "investigating", "needs-info"]
+
+[workflow.transitions]
+fixed-pending-release = ["fixed", "new"]
+"#;
+
+fn setup_workflow_env() -> tempfile::TempDir {
+    let work_dir = setup_test_env();
+    let path = work_dir.path().join("asc-crashes/config.toml");
+    let config = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, config + WORKFLOW).unwrap();
+    work_dir
+}
+
+#[test]
+fn status_command_uses_configured_workflow() {
+    let work_dir = setup_workflow_env();
+    let a = seed_crash(&work_dir, None).to_string();
+    let b = seed_crash(&work_dir, None).to_string();
+
+    let crash = run_json(
+        &work_dir,
+        &["status", &a, "needs-info", "--notes", "which iOS?"],
+    );
+    assert_eq!(crash["status"], "needs-info");
+    run_json(&work_dir, &["status", &b, "fixed-pending-release"]);
+
+    let (_, err) = run_failing(&work_dir, &["status", &a, "blocked"]);
+    assert!(err["message"].as_str().unwrap().contains("unknown status"));
+    let (_, err) = run_failing(&work_dir, &["wontfix", &b]);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("can't move from 'fixed-pending-release' to 'wontfix'"));
+    run_json(&work_dir, &["fix", &b]);
+
+    let stats = run_json(&work_dir, &["stats"]);
+    assert_eq!(stats["by_status"]["needs-info"], 1);
+    assert_eq!(stats["unfixed"], 1);
+    let open = run_json(&work_dir, &["list", "--status", "needs-info"]);
+    assert_eq!(open["count"], 1);
+}
+
+#[test]
+fn invalid_workflow_config_is_rejected() {
+    let work_dir = setup_test_env();
+    let path = work_dir.path().join("asc-crashes/config.toml");
+    let config = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(
+        &path,
+        config + "[workflow]\nstatuses = [\"new\", \"fixed\"]\n",
+    )
+    .unwrap();
+    let (code, err) = run_failing(&work_dir, &["list"]);
+    assert_eq!(code, 1);
+    assert!(format!("{err}").contains("must include 'duplicate'"));
+}
+
+/// Replace the database with the crashes table as first shipped, before
+/// versioned migrations, with statuses pinned by a CHECK.
+fn write_first_release_db(db_path: &std::path::Path) {
+    std::fs::remove_file(db_path).unwrap();
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE apps (
+             id        INTEGER PRIMARY KEY AUTOINCREMENT,
+             asc_id    TEXT UNIQUE,
+             bundle_id TEXT UNIQUE NOT NULL,
+             name      TEXT
+         );
+         INSERT INTO apps (bundle_id, asc_id) VALUES ('com.test.app', '1');
+         CREATE TABLE crashes (
+             id              INTEGER PRIMARY KEY AUTOINCREMENT,
+             app_id          INTEGER NOT NULL REFERENCES apps(id),
+             submission_id   TEXT UNIQUE NOT NULL,
+             created_at      TEXT NOT NULL,
+             synced_at       TEXT NOT NULL DEFAULT (datetime('now')),
+             device_model    TEXT,
+             os_version      TEXT,
+             app_platform    TEXT,
+             architecture    TEXT,
+             tester_email    TEXT,
+             tester_comment  TEXT,
+             bundle_id       TEXT,
+             build_id        TEXT,
+             app_uptime_ms   INTEGER,
+             battery_pct     INTEGER,
+             connection_type TEXT,
+             has_log         INTEGER DEFAULT 0,
+             log_path        TEXT,
+             status          TEXT DEFAULT 'new'
+                             CHECK(status IN ('new','investigating','fixed','wontfix','duplicate')),
+             fixed_at        TEXT,
+             fix_notes       TEXT,
+             duplicate_of    INTEGER REFERENCES crashes(id)
+         );
+         CREATE INDEX idx_crashes_status ON crashes(status);
+         INSERT INTO crashes (app_id, submission_id, created_at, status)
+         VALUES (1, 'sub-0', '2025-06-12T16:41:07+00:00', 'investigating');",
+    )
+    .unwrap();
+    drop(conn);
+}
+
+#[test]
+fn old_databases_accept_custom_statuses() {
+    let work_dir = setup_workflow_env();
+    let db_path = work_dir.path().join("asc-crashes/crashes.db");
+    write_first_release_db(&db_path);
+
+    let crash = run_json(&work_dir, &["status", "1", "needs-info"]);
+    assert_eq!(crash["status"], "needs-info");
+    assert_eq!(crash["submission_id"], "sub-0");
+
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let indexed: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'idx_crashes_status'",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(indexed, 1);
+}
+
+#[test]
+fn db_migrate_versions_and_backs_up_old_databases() {
+    let work_dir = setup_test_env();
+    let fresh = run_json(&work_dir, &["db", "version"]);
+    assert_eq!(fresh["version"], fresh["latest"]);
+    assert_eq!(fresh["pending"].as_array().unwrap().len(), 0);
+
+    let data_dir = work_dir.path().join("asc-crashes");
+    let db_path = data_dir.join("crashes.db");
+    write_first_release_db(&db_path);
+    let user_version = || -> i64 {
+        rusqlite::Connection::open(&db_path)
+            .unwrap()
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .unwrap()
+    };
+
+    let old = run_json(&work_dir, &["db", "version"]);
+    assert_eq!(old["version"], 0);
+    let plan = run_json(&work_dir, &["db", "migrate", "--dry-run"]);
+    assert_eq!(plan["from"], 0);
+    assert_eq!(plan["pending"][0]["version"], 1);
+    assert_eq!(user_version(), 0, "dry run must not migrate");
+    assert!(!data_dir.join("backups").exists());
+
+    let report = run_json(&work_dir, &["db", "migrate"]);
+    assert_eq!(report["from"], 0);
+    assert_eq!(report["to"], fresh["latest"]);
+    let backup = std::path::PathBuf::from(report["backup"].as_str().unwrap());
+    assert!(backup.starts_with(data_dir.join("backups")));
+    let backed_up: String = rusqlite::Connection::open(&backup)
+        .unwrap()
+        .query_row("SELECT status FROM crashes WHERE id = 1", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(backed_up, "investigating");
+    assert_eq!(user_version(), fresh["latest"].as_i64().unwrap());
+
+    let again = run_json(&work_dir, &["db", "migrate"]);
+    assert_eq!(again["applied"].as_array().unwrap().len(), 0);
+    assert!(again["backup"].is_null());
+    let crash = run_json(&work_dir, &["show", "1"]);
+    assert_eq!(crash["status"], "investigating");
+
+    // A database from just before versioning keeps the steps it already has.
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    conn.execute_batch(
+        "DROP TRIGGER search_crash_insert; DROP TRIGGER search_crash_update;
+         DROP TRIGGER search_crash_delete; DROP TRIGGER search_feedback_insert;
+         DROP TRIGGER search_feedback_update; DROP TRIGGER search_feedback_delete;
+         DROP TRIGGER search_comment_insert; DROP TABLE search_index;
+         PRAGMA user_version = 0;",
+    )
+    .unwrap();
+    drop(conn);
+    let plan = run_json(&work_dir, &["db", "migrate", "--dry-run"]);
+    let versions: Vec<&serde_json::Value> = plan["pending"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| &s["version"])
+        .collect();
+    assert_eq!(versions, [&serde_json::json!(1), &fresh["latest"]]);
+    let report = run_json(&work_dir, &["db", "migrate"]);
+    assert_eq!(report["to"], fresh["latest"]);
+    assert_eq!(run_json(&work_dir, &["search", "anything"])["count"], 0);
+}
+
+#[test]
+fn search_ranks_logs_comments_and_notes_across_crashes_and_feedback() {
+    let work_dir = setup_test_env();
+    let in_log = seed_crash(&work_dir, Some("crash.ips"));
+    let in_comment = seed_crash(&work_dir, None);
+    run_json(&work_dir, &["reparse"]);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute_batch(
+        "UPDATE crashes SET tester_comment = 'Opened settings twice' WHERE id = 2;
+         INSERT INTO feedbacks (app_id, submission_id, created_at, tester_comment)
+         VALUES (1, 'fb-0', '2025-06-13T09:00:00+00:00', 'Settings title is cut off');",
+    )
+    .unwrap();
+    drop(conn);
+    run_json(&work_dir, &["note", "2", "CoreData fault in SettingsStore"]);
+
+    let found = run_json(&work_dir, &["search", "SettingsViewController"]);
+    assert_eq!(found["count"], 1);
+    assert_eq!(found["results"][0]["entity"], "crash");
+    assert_eq!(found["results"][0]["id"], in_log);
+    assert!(found["results"][0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("[SettingsViewController]"));
+
+    let settings = run_json(&work_dir, &["search", "settings"]);
+    let entities: Vec<&str> = settings["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| h["entity"].as_str().unwrap())
+        .collect();
+    assert!(entities.contains(&"crash") && entities.contains(&"feedback"));
+    let crashes_only = run_json(&work_dir, &["search", "settings", "--no-feedback"]);
+    assert!(crashes_only["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|h| h["entity"] == "crash"));
+
+    let note = run_json(&work_dir, &["search", "coredata"]);
+    assert_eq!(note["results"][0]["id"], in_comment);
+    let prefix = run_json(&work_dir, &["search", "SettingsSt*"]);
+    assert_eq!(prefix["count"], 1);
+
+    // Punctuation is matched literally unless --raw.
+    run_json(
+        &work_dir,
+        &["search", "-[UIViewController loadViewIfRequired]"],
+    );
+    let (code, _) = run_failing(&work_dir, &["search", "--raw", "settings AND ("]);
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn list_where_sort_and_paging() {
+    let work_dir = setup_test_env();
+    for _ in 0..4 {
+        seed_crash(&work_dir, None);
+    }
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute_batch(
+        "UPDATE crashes SET device_model = 'iPad14,1', os_version = '17.4', battery_pct = 12,
+                created_at = '2025-06-01T08:00:00+00:00', build_id = 'b-1' WHERE id = 1;
+         UPDATE crashes SET device_model = 'iPad13,4', os_version = '16.7', battery_pct = 8,
+                created_at = '2025-06-10T08:00:00+00:00' WHERE id = 2;
+         UPDATE crashes SET device_model = 'iPad14,1', os_version = '17.10', battery_pct = 55,
+                created_at = '2025-06-30T23:00:00+00:00' WHERE id = 3;
+         UPDATE crashes SET os_version = '9.3', battery_pct = 3,
+                created_at = '2025-07-02T08:00:00+00:00' WHERE id = 4;
+         INSERT INTO feedbacks (app_id, submission_id, created_at, device_model, tester_comment)
+         VALUES (1, 'fb-0', '2025-06-13T09:00:00+00:00', 'iPad14,1', 'Crashed on launch'),
+                (1, 'fb-1', '2025-06-14T09:00:00+00:00', 'iPhone16,2', NULL);",
+    )
+    .unwrap();
+    drop(conn);
+    let ids = |out: &serde_json::Value, key: &str| -> Vec<i64> {
+        out[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    let low_ipads = run_json(
+        &work_dir,
+        &[
+            "list",
+            "--where",
+            r#"device_model ~ "ipad%" and os_version >= "17" and battery_pct < 20"#,
+        ],
+    );
+    assert_eq!(ids(&low_ipads, "crashes"), [1]);
+    // Versions compare by number, not as text: "9.3" < "17" and "17.9" < "17.10".
+    let ios17 = run_json(&work_dir, &["list", "--where", r#"os_version >= "17""#]);
+    assert_eq!(ids(&ios17, "crashes"), [3, 1]);
+    let late = run_json(&work_dir, &["list", "--where", r#"os_version > "17.9""#]);
+    assert_eq!(ids(&late, "crashes"), [3]);
+    let by_os = run_json(&work_dir, &["list", "--sort", "os_version"]);
+    assert_eq!(ids(&by_os, "crashes"), [4, 2, 1, 3]);
+    let either = run_json(
+        &work_dir,
+        &["list", "--where", "battery_pct < 10 and not (id in (4))"],
+    );
+    assert_eq!(ids(&either, "crashes"), [2]);
+
+    let by_battery = run_json(&work_dir, &["list", "--sort", "battery_pct:desc"]);
+    assert_eq!(ids(&by_battery, "crashes"), [3, 1, 2, 4]);
+    let page = run_json(
+        &work_dir,
+        &[
+            "list",
+            "--sort",
+            "battery_pct",
+            "--limit",
+            "2",
+            "--offset",
+            "2",
+        ],
+    );
+    assert_eq!(ids(&page, "crashes"), [1, 3]);
+
+    let june = run_json(
+        &work_dir,
+        &["list", "--since", "2025-06-05", "--until", "2025-06-30"],
+    );
+    assert_eq!(ids(&june, "crashes"), [3, 2]);
+    let build = run_json(&work_dir, &["list", "--build", "b-1"]);
+    assert_eq!(ids(&build, "crashes"), [1]);
+
+    run_json(&work_dir, &["feedback", "tag", "2", "layout"]);
+    let feedback = run_json(
+        &work_dir,
+        &[
+            "feedback",
+            "list",
+            "--where",
+            r#"device_model ~ "iPad%" or tags = "layout""#,
+        ],
+    );
+    assert_eq!(ids(&feedback, "feedbacks"), [2, 1]);
+    // `not (…)` keeps rows with no value, like `!~` does.
+    for filter in [
+        r#"not (tester_comment ~ "%crash%")"#,
+        r#"tester_comment !~ "%crash%""#,
+    ] {
+        let out = run_json(&work_dir, &["feedback", "list", "--where", filter]);
+        assert_eq!(ids(&out, "feedbacks"), [2], "{filter}");
+    }
+    let negated = run_json(
+        &work_dir,
+        &["list", "--where", r#"not (os_version = "17.4")"#],
+    );
+    let unequal = run_json(&work_dir, &["list", "--where", r#"os_version != "17.4""#]);
+    assert_eq!(ids(&negated, "crashes"), ids(&unequal, "crashes"));
+
+    // Values are bound, never spliced into the SQL.
+    let quoted = run_json(
+        &work_dir,
+        &["list", "--where", r#"device_model = "x' OR 1=1 --""#],
+    );
+    assert_eq!(quoted["count"], 0);
+
+    let (_, err) = run_failing(&work_dir, &["list", "--where", "battery_pct <"]);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("expected a value"));
+    let (_, err) = run_failing(&work_dir, &["list", "--where", "colour = 1"]);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown field 'colour'"));
+    let (_, err) = run_failing(&work_dir, &["list", "--sort", "tags"]);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("can't sort by 'tags'"));
+}
+
+const VIEWS: &str = r#"
+[[views]]
+name = "low-battery"
+filter = "battery_pct < 20"
+sort = "battery_pct"
+columns = ["id", "battery_pct", "tags"]
+
+[[views]]
+name = "ipad-feedback"
+entity = "feedback"
+filter = 'device_model ~ "iPad%"'
+"#;
+
+#[test]
+fn views_run_saved_queries_from_config() {
+    let work_dir = setup_test_env();
+    let path = work_dir.path().join("asc-crashes/config.toml");
+    let config = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, config.clone() + VIEWS).unwrap();
+    for _ in 0..3 {
+        seed_crash(&work_dir, None);
+    }
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute_batch(
+        "UPDATE crashes SET battery_pct = 15 WHERE id = 1;
+         UPDATE crashes SET battery_pct = 80 WHERE id = 2;
+         UPDATE crashes SET battery_pct = 4 WHERE id = 3;
+         INSERT INTO feedbacks (app_id, submission_id, created_at, device_model)
+         VALUES (1, 'fb-0', '2025-06-13T09:00:00+00:00', 'iPad14,1'),
+                (1, 'fb-1', '2025-06-14T09:00:00+00:00', 'iPhone16,2');",
+    )
+    .unwrap();
+    drop(conn);
+    run_json(&work_dir, &["tag", "3", "p0"]);
+
+    let listed = run_json(&work_dir, &["view"]);
+    assert_eq!(listed["count"], 2);
+    assert_eq!(listed["views"][1]["entity"], "feedback");
+
+    let low = run_json(&work_dir, &["view", "low-battery"]);
+    assert_eq!(
+        low["crashes"],
+        serde_json::json!([
+            {"id": 3, "battery_pct": 4, "tags": ["p0"]},
+            {"id": 1, "battery_pct": 15, "tags": []},
+        ])
+    );
+    let paged = run_json(
+        &work_dir,
+        &["view", "low-battery", "--limit", "1", "--offset", "1"],
+    );
+    assert_eq!(paged["crashes"][0]["id"], 1);
+
+    let ipads = run_json(&work_dir, &["view", "ipad-feedback"]);
+    assert_eq!(ipads["count"], 1);
+    assert_eq!(ipads["feedbacks"][0]["submission_id"], "fb-0");
+
+    let (_, err) = run_failing(&work_dir, &["view", "nope"]);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("configured: low-battery, ipad-feedback"));
+
+    let bad = "\n[[views]]\nname = \"broken\"\nfilter = \"battery_pct <\"\n";
+    std::fs::write(&path, config + bad).unwrap();
+    let (_, err) = run_failing(&work_dir, &["view"]);
+    assert!(err["message"].as_str().unwrap().contains("invalid filter"));
+}
+
+fn run_stdout(work_dir: &tempfile::TempDir, args: &[&str]) -> String {
+    let output = bin()
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+        ])
+        .args(args)
+        .output()
+        .expect("failed to run");
+    assert!(
+        output.status.success(),
+        "{:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn tabular_formats_for_lists_stats_and_groups() {
+    let work_dir = setup_test_env();
+    seed_crash(&work_dir, Some("crash.ips"));
+    seed_crash(&work_dir, None);
+    run_json(&work_dir, &["reparse"]);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute(
+        "UPDATE crashes SET tester_comment = 'tapped \"Save\", then | froze' WHERE id = 2",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+    run_json(&work_dir, &["tag", "1", "p0", "ui"]);
+
+    let csv = run_stdout(&work_dir, &["list", "--format", "csv"]);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    let header: Vec<&str> = lines[0].split(',').collect();
+    assert_eq!(&header[..3], ["id", "app_id", "submission_id"]);
+    assert_eq!(header.last(), Some(&"tags"));
+    assert!(lines[1].starts_with("2,1,sub-1,"));
+    assert!(lines[1].contains(r#","tapped ""Save"", then | froze","#));
+    assert!(lines[2].ends_with(r#","p0,ui""#));
+
+    let ndjson = run_stdout(&work_dir, &["list", "--format", "ndjson"]);
+    let rows: Vec<serde_json::Value> = ndjson
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["tags"], serde_json::json!(["p0", "ui"]));
+    assert!(ndjson.starts_with(r#"{"id":2,"app_id":1,"#));
+
+    let md = run_stdout(&work_dir, &["list", "--format", "markdown"]);
+    let md: Vec<&str> = md.lines().collect();
+    assert_eq!(
+        md[0],
+        "| id | status | created_at | device_model | os_version | build | exception_type | app_bundle_id |"
+    );
+    assert_eq!(md[1], "|---|---|---|---|---|---|---|---|");
+    assert!(md[3].contains("| EXC_BAD_ACCESS |"));
+
+    let stats = run_stdout(&work_dir, &["stats", "--format", "csv"]);
+    assert!(stats.starts_with("section,value,count\ntotal,,2\nstatus,new,2\nunfixed,,2\n"));
+    assert!(stats.contains("\ntag,p0,1\n"));
+
+    let groups = run_stdout(&work_dir, &["groups", "list", "--format", "ndjson"]);
+    let group: serde_json::Value = serde_json::from_str(groups.lines().next().unwrap()).unwrap();
+    assert_eq!(group["crash_count"], 1);
+
+    let feedback = run_stdout(&work_dir, &["feedback", "list", "--format", "csv"]);
+    assert!(feedback.starts_with("id,app_id,"));
+    assert_eq!(feedback.lines().count(), 1);
+
+    let (code, err) = run_failing(&work_dir, &["show", "1", "--format", "csv"]);
+    assert_eq!(code, 1);
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("use text or json"));
+}
+
+#[test]
+fn list_columns_pick_fields_and_fit_the_terminal() {
+    let work_dir = setup_test_env();
+    seed_crash(&work_dir, Some("crash.ips"));
+    run_json(&work_dir, &["reparse"]);
+    let conn = rusqlite::Connection::open(work_dir.path().join("asc-crashes/crashes.db")).unwrap();
+    conn.execute(
+        "UPDATE crashes SET tester_email = 'someone.with.a.long.address@example.com'",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+    let columns = "id,status,device_model,build,signature,tester_email";
+
+    let csv = run_stdout(
+        &work_dir,
+        &["list", "--columns", columns, "--format", "csv"],
+    );
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "id,status,device_model,build,signature,tester_email"
+    );
+    assert!(lines[1].starts_with("1,new,"));
+    assert!(lines[1].ends_with(",someone.with.a.long.address@example.com"));
+
+    // Keys come out in the order asked for, which parsing doesn't keep.
+    let json = run_stdout(
+        &work_dir,
+        &["list", "--columns", "id,build", "--format", "json"],
+    );
+    let (id, build) = (
+        json.find("\"id\"").unwrap(),
+        json.find("\"build\"").unwrap(),
+    );
+    assert!(id < build, "{json}");
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["count"], 1);
+    assert_eq!(json["crashes"][0]["id"], 1);
+
+    // Piped output is never cut; a narrow terminal truncates the widest cells.
+    let wide = run_stdout(&work_dir, &["list", "--columns", columns]);
+    assert!(wide.contains("someone.with.a.long.address@example.com"));
+    let output = bin()
+        .env("COLUMNS", "60")
+        .args([
+            "--data-dir",
+            work_dir.path().join("asc-crashes").to_str().unwrap(),
+            "list",
+            "--columns",
+            columns,
+        ])
+        .output()
+        .unwrap();
+    let narrow = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = narrow.lines().collect();
+    assert!(lines[0].starts_with(" ID STATUS DEVICE_MODEL"));
+    assert!(lines[2].contains('…'), "{narrow}");
+    assert!(
+        lines[..3].iter().all(|l| l.chars().count() <= 60),
+        "{narrow}"
+    );
+
+    let feedback = run_stdout(
+        &work_dir,
+        &[
+            "feedback",
+            "list",
+            "--columns",
+            "id,build,tester_comment",
+            "--format",
+            "csv",
+        ],
+    );
+    assert_eq!(feedback, "id,build,tester_comment\n");
+
+    let (code, err) = run_failing(&work_dir, &["list", "--columns", "id,nope"]);
+    assert_eq!(code, 1);
+    let message = err["message"].as_str().unwrap();
+    assert!(message.contains("unknown column 'nope'"), "{message}");
+    assert!(message.ends_with(", tags, build"), "{message}");
+}
@@ -0,0 +1,372 @@
+//! A local stand-in for the App Store Connect API, so tests can run `sync`
+//! and `apps` against canned responses.
+//!
+//! Responses come from `tests/fixtures/asc/`; `{base}` in a body is replaced
+//! with the server's URL, so pagination `links.next` points back here.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const APP_ID: &str = "1000000001";
+
+#[derive(Clone)]
+pub struct Reply {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Duration,
+}
+
+impl Reply {
+    /// A 200 with the contents of `tests/fixtures/asc/{name}`.
+    pub fn fixture(name: &str) -> Self {
+        let path = fixtures_dir().join(name);
+        let body = std::fs::read(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        let content_type = if name.ends_with(".json") {
+            "application/json"
+        } else {
+            "image/png"
+        };
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".into(), content_type.into())],
+            body,
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Hold the reply back for `ms`, so concurrent requests overlap.
+    pub fn delayed(mut self, ms: u64) -> Self {
+        self.delay = Duration::from_millis(ms);
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// A 200 with an inline JSON body.
+    pub fn json(body: &str) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: body.as_bytes().to_vec(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// A last page of crash submissions, one per `(id, createdDate, build)`.
+    pub fn crash_page(crashes: &[(&str, &str, Option<&str>)]) -> Self {
+        let data: Vec<serde_json::Value> = crashes
+            .iter()
+            .map(|(id, created, build)| {
+                serde_json::json!({
+                    "type": "betaFeedbackCrashSubmissions",
+                    "id": id,
+                    "attributes": {
+                        "createdDate": created,
+                        "deviceModel": "iPhone16,2",
+                        "osVersion": "18.5",
+                        "buildBundleId": "com.test.app"
+                    },
+                    "relationships": {
+                        "build": {
+                            "data": build.map(|b| serde_json::json!({"type": "builds", "id": b}))
+                        }
+                    }
+                })
+            })
+            .collect();
+        Self::json(&serde_json::json!({ "data": data, "links": {} }).to_string())
+    }
+
+    /// Metadata for build `id` (version 2.3.1) uploaded at `uploaded`.
+    pub fn build(id: &str, number: &str, uploaded: &str) -> Self {
+        Self::json(
+            &serde_json::json!({
+                "data": {
+                    "type": "builds",
+                    "id": id,
+                    "attributes": { "version": number, "uploadedDate": uploaded },
+                    "relationships": {
+                        "preReleaseVersion": { "data": { "type": "preReleaseVersions", "id": "prv-1" } }
+                    }
+                },
+                "included": [{
+                    "type": "preReleaseVersions",
+                    "id": "prv-1",
+                    "attributes": { "version": "2.3.1", "platform": "IOS" }
+                }]
+            })
+            .to_string(),
+        )
+    }
+
+    pub fn not_found() -> Self {
+        Self::json(
+            r#"{"errors":[{"status":"404","code":"NOT_FOUND","title":"The specified resource does not exist"}]}"#,
+        )
+        .status(404)
+    }
+
+    pub fn rate_limited() -> Self {
+        Self::fixture("error_429.json")
+            .status(429)
+            .header("Retry-After", "0")
+    }
+}
+
+struct Route {
+    path: String,
+    /// Must appear in the query string, e.g. `cursor=2`.
+    query: Option<String>,
+    /// Served in order; the last one repeats.
+    replies: VecDeque<Reply>,
+    hits: usize,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    /// `(path, Authorization header)` of every request, in arrival order.
+    requests: Vec<(String, String)>,
+    in_flight: usize,
+    max_in_flight: usize,
+}
+
+pub struct MockAsc {
+    base: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockAsc {
+    /// An empty server on a random local port. Unrouted requests get a 404.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let (shared, url) = (state.clone(), base.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (shared, url) = (shared.clone(), url.clone());
+                std::thread::spawn(move || serve(stream, &shared, &url));
+            }
+        });
+        Self { base, state }
+    }
+
+    /// A server for one app (`com.test.app`) with two pages of crashes, one
+    /// page of screenshot feedback, and their logs, screenshot and build.
+    /// `crash-2`'s log isn't available yet (404).
+    pub fn with_fixtures() -> Self {
+        let mock = Self::start();
+        let crashes = format!("/v1/apps/{APP_ID}/betaFeedbackCrashSubmissions");
+        mock.on("/v1/apps", Reply::fixture("apps.json"))
+            .on(&crashes, Reply::fixture("crashes_page1.json"))
+            .on(
+                &format!("{crashes}?cursor=2"),
+                Reply::fixture("crashes_page2.json"),
+            )
+            .on(
+                "/v1/betaFeedbackCrashSubmissions/crash-3/crashLog",
+                Reply::fixture("crash_log.json"),
+            )
+            .on(
+                "/v1/betaFeedbackCrashSubmissions/crash-2/crashLog",
+                Reply::not_found(),
+            )
+            .on(
+                "/v1/betaFeedbackCrashSubmissions/crash-1/crashLog",
+                Reply::fixture("crash_log.json"),
+            )
+            .on(
+                &format!("/v1/apps/{APP_ID}/betaFeedbackScreenshotSubmissions"),
+                Reply::fixture("screenshots.json"),
+            )
+            .on(
+                "/v1/betaFeedbackScreenshotSubmissions/shot-1/screenshot",
+                Reply::fixture("screenshot.png"),
+            )
+            .on("/v1/builds/build-412", Reply::fixture("build.json"));
+        mock
+    }
+
+    pub fn url(&self) -> &str {
+        &self.base
+    }
+
+    /// Answer `route` (`/path`, or `/path?needle` to also match the query
+    /// string) with `reply`, replacing whatever it answered before.
+    pub fn on(&self, route: &str, reply: Reply) -> &Self {
+        let (path, query) = split_route(route);
+        let mut state = self.state.lock().unwrap();
+        match state
+            .routes
+            .iter_mut()
+            .find(|r| r.path == path && r.query == query)
+        {
+            Some(r) => r.replies = VecDeque::from([reply]),
+            None => state.routes.push(Route {
+                path,
+                query,
+                replies: VecDeque::from([reply]),
+                hits: 0,
+            }),
+        }
+        drop(state);
+        self
+    }
+
+    /// Queue `reply` for `route` after the ones already set, so
+    /// `on(r, a).then(r, b)` serves `a` once and then `b` from then on.
+    pub fn then(&self, route: &str, reply: Reply) -> &Self {
+        let (path, query) = split_route(route);
+        let mut state = self.state.lock().unwrap();
+        let r = state
+            .routes
+            .iter_mut()
+            .find(|r| r.path == path && r.query == query)
+            .expect("then() without on()");
+        r.replies.push_back(reply);
+        drop(state);
+        self
+    }
+
+    /// The most requests that were being answered at the same time.
+    pub fn max_in_flight(&self) -> usize {
+        self.state.lock().unwrap().max_in_flight
+    }
+
+    /// The bearer tokens requests arrived with, one per request in order.
+    pub fn tokens(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .requests
+            .iter()
+            .map(|(_, auth)| auth.trim_start_matches("Bearer ").to_string())
+            .collect()
+    }
+
+    /// Position of the first request for `path` among all requests, as an
+    /// index into `tokens()`.
+    pub fn first_request(&self, path: &str) -> usize {
+        let state = self.state.lock().unwrap();
+        state
+            .requests
+            .iter()
+            .position(|(p, _)| p == path)
+            .unwrap_or_else(|| panic!("no request for {path}"))
+    }
+
+    /// How many requests `route` has answered.
+    pub fn hits(&self, route: &str) -> usize {
+        let (path, query) = split_route(route);
+        let state = self.state.lock().unwrap();
+        state
+            .routes
+            .iter()
+            .find(|r| r.path == path && r.query == query)
+            .map_or(0, |r| r.hits)
+    }
+}
+
+fn split_route(route: &str) -> (String, Option<String>) {
+    match route.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (route.to_string(), None),
+    }
+}
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/asc")
+}
+
+fn serve(stream: TcpStream, state: &Mutex<State>, base: &str) {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // Read headers up to the blank line; every request the client makes is
+    // a body-less GET.
+    let mut authorization = String::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("authorization") {
+                authorization = value.trim().to_string();
+            }
+        }
+        line.clear();
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    state
+        .lock()
+        .unwrap()
+        .requests
+        .push((path.to_string(), authorization));
+    let reply = route(state, path, query);
+    {
+        let mut state = state.lock().unwrap();
+        state.in_flight += 1;
+        state.max_in_flight = state.max_in_flight.max(state.in_flight);
+    }
+    std::thread::sleep(reply.delay);
+
+    let body = if reply.headers.iter().any(|(_, v)| v == "application/json") {
+        String::from_utf8_lossy(&reply.body)
+            .replace("{base}", base)
+            .into_bytes()
+    } else {
+        reply.body
+    };
+    let mut out = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        reply.status,
+        body.len()
+    );
+    for (name, value) in &reply.headers {
+        out.push_str(&format!("{name}: {value}\r\n"));
+    }
+    out.push_str("\r\n");
+    let mut stream = &stream;
+    let _ = stream.write_all(out.as_bytes());
+    let _ = stream.write_all(&body);
+    state.lock().unwrap().in_flight -= 1;
+}
+
+/// The most specific matching route's next reply: routes with a query
+/// needle win over the bare path.
+fn route(state: &Mutex<State>, path: &str, query: &str) -> Reply {
+    let mut state = state.lock().unwrap();
+    let found = state
+        .routes
+        .iter_mut()
+        .filter(|r| r.path == path)
+        .filter(|r| r.query.as_deref().is_none_or(|q| query.contains(q)))
+        .max_by_key(|r| r.query.is_some());
+    match found {
+        Some(r) => {
+            r.hits += 1;
+            if r.replies.len() > 1 {
+                r.replies.pop_front().unwrap()
+            } else {
+                r.replies[0].clone()
+            }
+        }
+        None => Reply::not_found(),
+    }
+}